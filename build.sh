@@ -1,4 +1,15 @@
-wasm-pack build --target=web
+# Usage: ./build.sh [firefox|chromium] (defaults to firefox)
+# firefox builds in place, so extension/ can be loaded as-is. chromium copies extension/ over to
+# dist/chromium/ with the MV3 manifest instead.
+TARGET=${1:-firefox}
+
+case "$TARGET" in
+    firefox) FEATURES="" ;;
+    chromium) FEATURES="--no-default-features --features chromium" ;;
+    *) echo "Unknown target: $TARGET (expected firefox or chromium)"; exit 1 ;;
+esac
+
+wasm-pack build --target=web -- $FEATURES
 
 rm -r ./extension/snippets/ || true
 rm ./extension/touch_grass_bg.wasm || true
@@ -11,3 +22,12 @@ npx tailwindcss@3 -i ./src/extension_ui/input.css -o ./extension/output.css
 cp -r ./pkg/snippets ./extension/snippets
 cp ./pkg/touch_grass_bg.wasm ./extension/touch_grass_bg.wasm
 cp ./pkg/touch_grass.js ./extension/touch_grass.js
+
+if [ "$TARGET" = "chromium" ]; then
+    rm -r ./dist/chromium || true
+    mkdir -p ./dist
+    cp -r ./extension ./dist/chromium
+    mv ./dist/chromium/manifest.chromium.json ./dist/chromium/manifest.json
+    # MV3 runs background.js as a service worker, there's no background page.
+    rm ./dist/chromium/background.html
+fi
@@ -0,0 +1,37 @@
+console.log("Hello from extension/background.js");
+
+// Loaded from background.html on Firefox (MV2), and directly as the service worker on Chromium (MV3).
+import * as myModule from "./touch_grass.js";
+
+const api = globalThis.browser ?? globalThis.chrome;
+
+// An MV3 service worker that got woken up by an event only gets to see it if the listener was added
+// synchronously, at the top level. Loading the .wasm is anything but, so the listeners live here and
+// hold on to whatever comes in until it's loaded. See `background_event` in src/background.rs.
+let wasmReady = false;
+const queued = [];
+
+function forward(event, data) {
+    if (wasmReady) {
+        myModule.background_event(event, data);
+    } else {
+        queued.push([event, data]);
+    }
+}
+
+api.alarms.onAlarm.addListener((alarm) => forward("alarm", alarm.name));
+api.storage.onChanged.addListener(() => forward("storage_changed"));
+api.permissions.onAdded.addListener(() => forward("permission_added"));
+api.notifications.onClicked.addListener((id) => forward("notification_clicked", id));
+// The badge doesn't survive a browser restart, and without this the worker wouldn't start until
+// the next alarm.
+api.runtime.onStartup.addListener(() => forward("startup"));
+
+(async () => {
+    await myModule.default("./touch_grass_bg.wasm");
+    wasmReady = true;
+    for (const [event, data] of queued.splice(0)) {
+        myModule.background_event(event, data);
+    }
+    await myModule.background_main();
+})();
@@ -2,8 +2,20 @@ console.log("Hello from extension/index.js");
 
 // Can't do the `import * as ...` thing cuz I'm not a top-level module here, I'm just a poor
 // little injected script.
+//
+// The guard is for custom sites overlapping the built-in ones (or each other), which gets us injected
+// more than once. Everything's inside the function for the same reason: a second top-level `const`
+// would be a redeclaration error.
 (async () => {
-    const myModule = await import(browser.runtime.getURL("./touch_grass.js"));
+    if (globalThis.__touchGrassLoaded) {
+        return;
+    }
+    globalThis.__touchGrassLoaded = true;
+
+    // Firefox calls it `browser`, Chromium `chrome`.
+    const extensionApi = globalThis.browser ?? globalThis.chrome;
+
+    const myModule = await import(extensionApi.runtime.getURL("./touch_grass.js"));
     await myModule.default();
     setTimeout(() => myModule.touch_grass(), 2000);
-})();
\ No newline at end of file
+})();
@@ -0,0 +1,6 @@
+import * as myModule from "./touch_grass.js";
+
+(async () => {
+    await myModule.default("./touch_grass_bg.wasm");
+    myModule.start_report();
+})();
@@ -0,0 +1,241 @@
+use crate::bindings::{self, browser, object};
+use crate::config::{self, Config, Site, StorageError};
+use crate::messages::Message;
+use crate::schedule::{self, BlockState, WeekTime};
+use crate::selector_pack::{self, PackError};
+use crate::{console_log, custom_sites, hard_block, i18n, notifications, report};
+use std::cell::Cell;
+use wasm_bindgen::prelude::*;
+use wasm_bindgen_futures::spawn_local;
+use web_extensions_sys::Tab;
+
+// Below this many minutes of budget left, the badge turns into a warning.
+const WARNING_THRESHOLD: u32 = 10;
+
+const BLOCKED_COLOR: &str = "#d93025";
+const WARNING_COLOR: &str = "#f29900";
+const ALLOWED_COLOR: &str = "#188038";
+
+const TICK_ALARM: &str = "touch-grass-tick";
+const BLOCK_START_ALARM: &str = "touch-grass-block-start";
+const BLOCK_END_ALARM: &str = "touch-grass-block-end";
+// The daily budget resets at midnight.
+const MIDNIGHT_ALARM: &str = "touch-grass-midnight";
+
+const YOUTUBE_URL_PATTERNS: [&str; 2] = ["*://*.youtube.com/*", "*://youtube.com/*"];
+
+thread_local! {
+    // What the YouTube tabs were last told. None = nothing broadcasted yet.
+    static LAST_BROADCAST: Cell<Option<bool>> = const { Cell::new(None) };
+}
+
+#[wasm_bindgen]
+pub async fn background_main() {
+    console_error_panic_hook::set_once();
+
+    // Both the clock and the stored usage move the badge (and the warnings): the former through the
+    // block window, the latter through the daily budget (the content script bumps it every minute).
+    // Alarms rather than setInterval, since they still fire on time after the machine wakes up.
+    let alarms = bindings::alarms();
+    alarms.create(TICK_ALARM, &object(&[("periodInMinutes", 1.into())]));
+
+    // The rest of the listeners are in extension/background.js, see `background_event()`. This one
+    // has to answer synchronously, which the forwarding can't do. It's only for Firefox (MV2), where
+    // the background page sticks around anyway.
+    hard_block::init();
+
+    refresh(false).await;
+    update_selector_pack().await;
+}
+
+/// Gets the browser events from extension/background.js, which has to listen for them itself: the MV3
+/// worker only gets woken up for listeners that were added before the .wasm could've been loaded.
+#[wasm_bindgen]
+pub fn background_event(event: &str, data: JsValue) {
+    // Can come in before `background_main()`.
+    console_error_panic_hook::set_once();
+
+    match event {
+        "alarm" => {
+            let name = data.as_string().unwrap_or_default();
+            // Boundaries always get broadcasted, even if we think the tabs already know. They
+            // might've been asleep, or loaded the page right before the boundary.
+            let force_broadcast = name != TICK_ALARM;
+            spawn_local(refresh(force_broadcast));
+            if name == MIDNIGHT_ALARM {
+                spawn_local(update_selector_pack());
+            }
+        }
+        "storage_changed" => spawn_local(refresh(false)),
+        // The popup asks for the host permission of a custom site at the same time as saving it, so
+        // the permission can land after we've already tried (and failed) to register it.
+        "permission_added" => {
+            custom_sites::forget_registrations();
+            spawn_local(refresh(false));
+        }
+        "notification_clicked" => {
+            if data.as_string().as_deref() == Some(report::NOTIFICATION_ID) {
+                spawn_local(report::open());
+            }
+        }
+        // The badge is gone after a browser restart.
+        "startup" => spawn_local(refresh(true)),
+        _ => {
+            console_log!("Unknown background event: {event}");
+        }
+    }
+}
+
+/// Checks `Config::selector_pack_url` for a newer selector pack. The content scripts pick it up on
+/// their next page load.
+async fn update_selector_pack() {
+    let Ok(storage) = config::get_storage().await else {
+        return;
+    };
+    let Some(url) = storage.user_config.selector_pack_url else {
+        return;
+    };
+    match selector_pack::install_from_url(&url).await {
+        Ok(pack) => {
+            console_log!("Updated to selector pack version {}", pack.version);
+        }
+        // The usual case, nothing new.
+        Err(PackError::Outdated { .. }) => {}
+        Err(e) => {
+            console_log!("{e}");
+        }
+    }
+}
+
+async fn refresh(force_broadcast: bool) {
+    match config::get_storage().await {
+        Ok(storage) => {
+            // Before anything that might put up a notification.
+            i18n::set_language(storage.user_config.language);
+            let usage = config::get_usage().await.unwrap_or_else(|e| {
+                console_log!("Couldn't get the usage, going by none: {e}");
+                Default::default()
+            });
+            let state = schedule::block_state(&storage.user_config, &usage, Site::YouTube);
+            update_badge(state);
+            schedule_boundary_alarms(&storage.user_config).await;
+            custom_sites::register_content_scripts(&storage.user_config).await;
+            notifications::check(&storage.user_config, &usage).await;
+            report::deliver_if_due(&storage, &usage).await;
+
+            let blocked = state.is_blocked();
+            hard_block::update(&storage.user_config, blocked).await;
+            if force_broadcast || LAST_BROADCAST.get() != Some(blocked) {
+                LAST_BROADCAST.set(Some(blocked));
+                broadcast(Message::BlockStateChanged { blocked }).await;
+            }
+        }
+        // Not set up yet, nothing to show.
+        Err(StorageError::EmptyStorage) => {}
+        Err(e) => {
+            console_log!("Couldn't refresh the background state: {e}");
+        }
+    }
+}
+
+/// (Re-)registers alarms for the next time the block window starts and ends. Alarms with the same
+/// name replace each other, so this is fine to call again whenever the config might've changed.
+async fn schedule_boundary_alarms(config: &Config) {
+    let schedule = config.youtube_schedule();
+    let alarms = bindings::alarms();
+    let now = js_sys::Date::now();
+    // Boundaries are on the minute, so count from the start of the current one.
+    let minute_start = now - now % (60.0 * 1000.0);
+    let at = |minutes: u32| minute_start + f64::from(minutes) * 60.0 * 1000.0;
+
+    for (name, blocked) in [(BLOCK_START_ALARM, true), (BLOCK_END_ALARM, false)] {
+        match schedule::minutes_until(&schedule, WeekTime::now(), blocked) {
+            Some(minutes) => alarms.create(name, &object(&[("when", at(minutes).into())])),
+            // E.g no active days at all: there's no boundary to wait for.
+            None => {
+                let _ = alarms.clear(name).await;
+            }
+        }
+    }
+
+    let today = js_sys::Date::new_0();
+    let midnight = js_sys::Date::new_with_year_month_day(
+        today.get_full_year(),
+        today.get_month() as i32,
+        today.get_date() as i32 + 1,
+    );
+    alarms.create(
+        MIDNIGHT_ALARM,
+        &object(&[("when", midnight.get_time().into())]),
+    );
+}
+
+/// Sends `message` to the content script of every open YouTube tab.
+async fn broadcast(message: Message) {
+    let patterns = js_sys::Array::new();
+    for pattern in YOUTUBE_URL_PATTERNS {
+        patterns.push(&JsValue::from_str(pattern));
+    }
+
+    let tabs = match browser()
+        .tabs()
+        .query(&object(&[("url", patterns.into())]))
+        .await
+    {
+        Ok(tabs) => js_sys::Array::from(&tabs),
+        Err(e) => {
+            console_log!("Couldn't look up the YouTube tabs: {e:?}");
+            return;
+        }
+    };
+
+    for tab in tabs.iter() {
+        let Some(tab_id) = tab.unchecked_into::<Tab>().id() else {
+            continue;
+        };
+        // Fails for tabs whose content script hasn't loaded yet. They'll work out the state
+        // themselves when it does, so that's fine.
+        let _ = browser()
+            .tabs()
+            .send_message(tab_id, &message.to_js(), None)
+            .await;
+    }
+}
+
+fn update_badge(state: BlockState) {
+    let action = bindings::browser_action();
+    let (text, color) = badge_for(state);
+
+    action.set_badge_text(&object(&[("text", text.into())]));
+    action.set_badge_background_color(&object(&[("color", color.into())]));
+}
+
+fn badge_for(state: BlockState) -> (String, &'static str) {
+    match state {
+        BlockState::BlockedBySchedule { minutes_left } => (
+            minutes_left.map(short_duration).unwrap_or_default(),
+            BLOCKED_COLOR,
+        ),
+        BlockState::BudgetExhausted => ("0m".to_owned(), BLOCKED_COLOR),
+        BlockState::Allowed {
+            budget_left: Some(budget_left),
+        } => {
+            let color = if budget_left < WARNING_THRESHOLD {
+                WARNING_COLOR
+            } else {
+                ALLOWED_COLOR
+            };
+            (short_duration(budget_left), color)
+        }
+        BlockState::Allowed { budget_left: None } => (String::new(), ALLOWED_COLOR),
+    }
+}
+
+// The badge only fits ~4 characters.
+fn short_duration(minutes: u32) -> String {
+    if minutes < 60 {
+        format!("{minutes}m")
+    } else {
+        format!("{}h", minutes / 60)
+    }
+}
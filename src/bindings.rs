@@ -0,0 +1,226 @@
+//! Bits of the WebExtension API that `web_extensions_sys` doesn't cover (yet), and papering over
+//! the differences between Firefox and Chromium.
+
+use js_sys::{Object, Reflect};
+use wasm_bindgen::prelude::*;
+use web_extensions_sys::Browser;
+
+#[cfg(all(feature = "firefox", feature = "chromium"))]
+compile_error!("Pick one of the `firefox` and `chromium` features, not both.");
+#[cfg(not(any(feature = "firefox", feature = "chromium")))]
+compile_error!("Pick one of the `firefox` and `chromium` features.");
+
+/// Name of the global that the extension API hangs off of.
+#[cfg(feature = "firefox")]
+pub const BROWSER_GLOBAL: &str = "browser";
+#[cfg(feature = "chromium")]
+pub const BROWSER_GLOBAL: &str = "chrome";
+
+/// `browser` on Firefox, `chrome` on Chromium. Same API either way (as far as we're concerned).
+pub fn browser() -> &'static Browser {
+    #[cfg(feature = "firefox")]
+    return web_extensions_sys::browser();
+    #[cfg(not(feature = "firefox"))]
+    return web_extensions_sys::chrome();
+}
+
+#[wasm_bindgen]
+extern "C" {
+    // https://developer.mozilla.org/en-US/docs/Mozilla/Add-ons/WebExtensions/API/browserAction
+    // Or `action`, on MV3. The badge bits are the same for both.
+    pub type BrowserAction;
+
+    #[wasm_bindgen(method, js_name = setBadgeText)]
+    pub fn set_badge_text(this: &BrowserAction, details: &Object);
+
+    #[wasm_bindgen(method, js_name = setBadgeBackgroundColor)]
+    pub fn set_badge_background_color(this: &BrowserAction, details: &Object);
+}
+
+#[wasm_bindgen]
+extern "C" {
+    // https://developer.mozilla.org/en-US/docs/Mozilla/Add-ons/WebExtensions/API/notifications
+    pub type Notifications;
+
+    #[wasm_bindgen(catch, method)]
+    pub async fn create(
+        this: &Notifications,
+        id: &str,
+        options: &Object,
+    ) -> Result<JsValue, JsValue>;
+}
+
+#[wasm_bindgen]
+extern "C" {
+    // https://developer.mozilla.org/en-US/docs/Mozilla/Add-ons/WebExtensions/API/alarms
+    pub type Alarms;
+
+    #[wasm_bindgen(method)]
+    pub fn create(this: &Alarms, name: &str, info: &Object);
+
+    #[wasm_bindgen(catch, method)]
+    pub async fn clear(this: &Alarms, name: &str) -> Result<JsValue, JsValue>;
+}
+
+#[wasm_bindgen]
+extern "C" {
+    // https://developer.mozilla.org/en-US/docs/Mozilla/Add-ons/WebExtensions/API/webRequest
+    pub type WebRequest;
+
+    #[wasm_bindgen(method, getter, js_name = onBeforeRequest)]
+    pub fn on_before_request(this: &WebRequest) -> WebRequestEvent;
+
+    // Unlike most events, these take a filter (and extra options) when adding a listener.
+    pub type WebRequestEvent;
+
+    #[wasm_bindgen(method, js_name = addListener)]
+    pub fn add_listener(
+        this: &WebRequestEvent,
+        listener: &js_sys::Function,
+        filter: &Object,
+        extra_info_spec: &js_sys::Array,
+    );
+
+    // https://developer.chrome.com/docs/extensions/reference/api/declarativeNetRequest
+    pub type DeclarativeNetRequest;
+
+    #[wasm_bindgen(catch, method, js_name = updateSessionRules)]
+    pub async fn update_session_rules(
+        this: &DeclarativeNetRequest,
+        options: &Object,
+    ) -> Result<JsValue, JsValue>;
+}
+
+#[wasm_bindgen]
+extern "C" {
+    // https://developer.mozilla.org/en-US/docs/Mozilla/Add-ons/WebExtensions/API/permissions
+    pub type Permissions;
+
+    // Has to be called straight from a user action (e.g a click handler), hence not `async`: that
+    // wouldn't make the call until first polled, which is too late.
+    #[wasm_bindgen(method)]
+    pub fn request(this: &Permissions, permissions: &Object) -> js_sys::Promise;
+}
+
+#[cfg(feature = "firefox")]
+#[wasm_bindgen]
+extern "C" {
+    // https://developer.mozilla.org/en-US/docs/Mozilla/Add-ons/WebExtensions/API/contentScripts
+    pub type ContentScripts;
+
+    #[wasm_bindgen(catch, method)]
+    pub async fn register(this: &ContentScripts, options: &Object) -> Result<JsValue, JsValue>;
+
+    // What `register` resolves to. The script stays registered until this is unregistered, or the
+    // background page goes away.
+    pub type RegisteredContentScript;
+
+    #[wasm_bindgen(catch, method)]
+    pub async fn unregister(this: &RegisteredContentScript) -> Result<JsValue, JsValue>;
+}
+
+#[cfg(feature = "chromium")]
+#[wasm_bindgen]
+extern "C" {
+    // https://developer.chrome.com/docs/extensions/reference/api/scripting
+    // web_extensions_sys' `Scripting` only has the one-off injection bits.
+    pub type ScriptRegistry;
+
+    #[wasm_bindgen(catch, method, js_name = registerContentScripts)]
+    pub async fn register_content_scripts(
+        this: &ScriptRegistry,
+        scripts: &js_sys::Array,
+    ) -> Result<JsValue, JsValue>;
+
+    #[wasm_bindgen(catch, method, js_name = unregisterContentScripts)]
+    pub async fn unregister_content_scripts(this: &ScriptRegistry) -> Result<JsValue, JsValue>;
+}
+
+#[wasm_bindgen]
+extern "C" {
+    // The global `fetch`, rather than `window.fetch`: MV3's service worker doesn't have a `window`.
+    #[wasm_bindgen(catch, js_name = fetch)]
+    pub async fn fetch(url: &str) -> Result<JsValue, JsValue>;
+}
+
+#[cfg(feature = "firefox")]
+pub fn web_request() -> WebRequest {
+    Reflect::get(browser(), &JsValue::from_str("webRequest"))
+        .expect("webRequest should exist given the \"webRequest\" permission")
+        .unchecked_into()
+}
+
+#[cfg(feature = "chromium")]
+pub fn declarative_net_request() -> DeclarativeNetRequest {
+    Reflect::get(browser(), &JsValue::from_str("declarativeNetRequest"))
+        .expect("declarativeNetRequest should exist given the \"declarativeNetRequest\" permission")
+        .unchecked_into()
+}
+
+pub fn permissions() -> Permissions {
+    Reflect::get(browser(), &JsValue::from_str("permissions"))
+        .expect("permissions should always exist")
+        .unchecked_into()
+}
+
+#[cfg(feature = "firefox")]
+pub fn content_scripts() -> ContentScripts {
+    Reflect::get(browser(), &JsValue::from_str("contentScripts"))
+        .expect("contentScripts should exist in the background page")
+        .unchecked_into()
+}
+
+#[cfg(feature = "chromium")]
+pub fn script_registry() -> ScriptRegistry {
+    Reflect::get(browser(), &JsValue::from_str("scripting"))
+        .expect("scripting should exist given the \"scripting\" permission")
+        .unchecked_into()
+}
+
+pub fn alarms() -> Alarms {
+    Reflect::get(browser(), &JsValue::from_str("alarms"))
+        .expect("alarms should exist given the \"alarms\" permission")
+        .unchecked_into()
+}
+
+pub fn notifications() -> Notifications {
+    Reflect::get(browser(), &JsValue::from_str("notifications"))
+        .expect("notifications should exist given the \"notifications\" permission")
+        .unchecked_into()
+}
+
+/// `runtime.getURL()`: the full URL of one of the extension's own files, e.g `report.html`.
+pub fn extension_url(path: &str) -> String {
+    let runtime = Reflect::get(browser(), &JsValue::from_str("runtime"))
+        .expect("runtime should always exist");
+    let get_url: js_sys::Function = Reflect::get(&runtime, &JsValue::from_str("getURL"))
+        .expect("runtime.getURL should always exist")
+        .unchecked_into();
+    get_url
+        .call1(&runtime, &JsValue::from_str(path))
+        .ok()
+        .and_then(|url| url.as_string())
+        .unwrap_or_default()
+}
+
+// web_extensions_sys' own `BrowserAction` only has `onClicked`, so grab the same object under our type.
+pub fn browser_action() -> BrowserAction {
+    #[cfg(feature = "firefox")]
+    const ACTION_KEY: &str = "browserAction"; // MV2
+    #[cfg(feature = "chromium")]
+    const ACTION_KEY: &str = "action"; // MV3
+
+    Reflect::get(browser(), &JsValue::from_str(ACTION_KEY))
+        .expect("the browser action should exist in the background page")
+        .unchecked_into()
+}
+
+/// `{ key: value, ... }`, for the many API calls that take a "details" object.
+pub fn object(entries: &[(&str, JsValue)]) -> Object {
+    let obj = Object::new();
+    for (key, value) in entries {
+        Reflect::set(&obj, &JsValue::from_str(key), value)
+            .expect("Setting a key on a fresh object");
+    }
+    obj
+}
@@ -1,22 +1,603 @@
+use crate::bindings::{browser, BROWSER_GLOBAL};
+use crate::i18n::{fill, t};
+use crate::session::Session;
 use crate::{config::storage_types::StorageSerdeWrapper, console_log};
-use serde::{Deserialize, Serialize};
+use serde::{de::DeserializeOwned, Deserialize, Serialize};
 use serde_wasm_bindgen as swb;
+use std::collections::BTreeMap;
 use std::error::Error;
 use wasm_bindgen::JsValue;
-use web_extensions_sys::browser;
+use web_extensions_sys::StorageArea;
 
+// #[serde(default)] so that configs saved by an older version of the extension (which won't have
+// the newer fields) still deserialize, instead of being flagged as corrupted and wiped.
 #[derive(Debug, Default, Clone, PartialEq, Eq, Deserialize, Serialize)]
+#[serde(default)]
 pub struct Config {
     pub block_time_start: u32, // Time in minutes
     pub block_time_end: u32,
     pub active_days: u8, // bitmap of active days
+    pub on_block: BlockAction,
+    pub feed_filter: FeedFilter,
+    /// If non-empty, during the block window watch pages are only usable for videos from these
+    /// channels (name or @handle).
+    pub allowed_channels: Vec<String>,
+    /// Minutes of YouTube allowed per day, on top of the block window. None = unlimited.
+    pub daily_budget: Option<u32>,
+    pub notifications: NotificationSettings,
+    /// Also block at the network level (see `hard_block`), which can't be undone from the page.
+    pub hard_block: bool,
+    pub shorts: ShortsSettings,
+    pub dull_mode: DullMode,
+    pub friction: FrictionSettings,
+    pub session_limit: SessionLimit,
+    pub weekly_report: WeeklyReport,
+    /// For the popup, the extension's other pages and the block screen.
+    pub theme: Theme,
+    /// For everything the extension shows, see `i18n`.
+    pub language: Language,
+    /// Schedules for every site other than YouTube, whose schedule is the fields up top (they
+    /// predate there being other sites).
+    pub sites: BTreeMap<Site, SiteConfig>,
+    pub custom_sites: Vec<CustomSite>,
+    /// Where to check for newer selector packs, once a day. None = only ever load them by hand.
+    pub selector_pack_url: Option<String>,
 }
 
 impl Config {
-    pub async fn get_config() -> Result<Config, StorageError> {
-        Ok(get_storage().await?.user_config)
+    pub fn youtube_schedule(&self) -> Schedule {
+        Schedule {
+            block_time_start: self.block_time_start,
+            block_time_end: self.block_time_end,
+            active_days: self.active_days,
+            daily_budget: self.daily_budget,
+        }
+    }
+
+    /// None if the user hasn't turned blocking on for `site`.
+    pub fn schedule(&self, site: Site) -> Option<Schedule> {
+        match site {
+            Site::YouTube => Some(self.youtube_schedule()),
+            _ => self
+                .sites
+                .get(&site)
+                .filter(|site_config| site_config.enabled)
+                .map(|site_config| site_config.schedule.clone()),
+        }
+    }
+}
+
+/// Longest a daily budget can be. Anything more is the same as no budget at all.
+pub const MAX_DAILY_BUDGET: u32 = 24 * 60;
+
+/// Furthest ahead a notification can warn. A day ahead is plenty.
+pub const MAX_LEAD_TIME: u32 = 24 * 60;
+
+/// Something wrong with a schedule that the settings form won't save.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ConfigProblem {
+    NoActiveDays {
+        what: String,
+    },
+    /// Start and end are the same, so it never blocks anything.
+    EmptyWindow {
+        what: String,
+    },
+    /// 0, or more than `MAX_DAILY_BUDGET`.
+    BudgetOutOfRange {
+        what: String,
+        budget: u32,
+    },
+    /// The on-block redirect has nowhere (sensible) to go.
+    BadRedirectTarget(RedirectProblem),
+    /// A session limit with no break in between sessions, i.e no limit at all.
+    NoSessionGap,
+    /// Notifications turned on, but never ahead of anything.
+    NoLeadTimes,
+    /// 0, or more than `MAX_LEAD_TIME`.
+    LeadTimeOutOfRange {
+        lead: u32,
+    },
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum RedirectProblem {
+    Missing,
+    /// Playlist IDs are only letters, digits, - and _.
+    BadPlaylistId,
+    /// Anything but http(s) could be a `javascript:` URL or the like.
+    NotHttp,
+    /// The YouTube homepage, which is what's getting redirected in the first place.
+    Loop,
+}
+
+impl RedirectProblem {
+    /// Without saying what it's about, for right under the input.
+    pub fn message(&self) -> &'static str {
+        match self {
+            RedirectProblem::Missing => t().redirect_target_missing,
+            RedirectProblem::BadPlaylistId => t().bad_playlist_id,
+            RedirectProblem::NotHttp => t().redirect_not_http,
+            RedirectProblem::Loop => t().redirect_loop,
+        }
+    }
+}
+
+impl std::fmt::Display for ConfigProblem {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let problem = match self {
+            ConfigProblem::NoActiveDays { what } => fill(t().problem_no_days, &[("what", what)]),
+            ConfigProblem::EmptyWindow { what } => {
+                fill(t().problem_empty_window, &[("what", what)])
+            }
+            ConfigProblem::BudgetOutOfRange { what, budget } => fill(
+                t().problem_budget,
+                &[
+                    ("what", what),
+                    ("budget", budget),
+                    ("max", &MAX_DAILY_BUDGET),
+                ],
+            ),
+            ConfigProblem::BadRedirectTarget(problem) => {
+                format!("{}: {}", t().when_blocked, problem.message())
+            }
+            ConfigProblem::NoSessionGap => t().problem_session_gap.to_owned(),
+            ConfigProblem::NoLeadTimes => t().problem_no_lead_times.to_owned(),
+            ConfigProblem::LeadTimeOutOfRange { lead } => fill(
+                t().problem_lead_time,
+                &[("lead", lead), ("max", &MAX_LEAD_TIME)],
+            ),
+        };
+        f.write_str(&problem)
+    }
+}
+
+impl Schedule {
+    /// `what` is what the schedule is for, to tell the problems apart.
+    pub fn problems(&self, what: &str) -> Vec<ConfigProblem> {
+        let mut problems = self.window_problems(what);
+        if let Some(budget) = self
+            .daily_budget
+            .filter(|budget| !(1..=MAX_DAILY_BUDGET).contains(budget))
+        {
+            problems.push(ConfigProblem::BudgetOutOfRange {
+                what: what.to_owned(),
+                budget,
+            });
+        }
+        problems
+    }
+
+    /// Same as `problems()`, minus the budget. For schedules that don't have one (dull mode).
+    pub fn window_problems(&self, what: &str) -> Vec<ConfigProblem> {
+        let mut problems = Vec::new();
+        if self.active_days & 0b111_1111 == 0 {
+            problems.push(ConfigProblem::NoActiveDays {
+                what: what.to_owned(),
+            });
+        }
+        if self.block_time_start == self.block_time_end {
+            problems.push(ConfigProblem::EmptyWindow {
+                what: what.to_owned(),
+            });
+        }
+        problems
+    }
+}
+
+impl Config {
+    /// Everything that should be fixed before saving. Schedules that are turned off don't count.
+    pub fn problems(&self) -> Vec<ConfigProblem> {
+        let mut problems = self.youtube_schedule().problems(Site::YouTube.name());
+        for (site, site_config) in &self.sites {
+            if site_config.enabled && *site != Site::YouTube {
+                problems.extend(site_config.schedule.problems(site.name()));
+            }
+        }
+        for site in self
+            .custom_sites
+            .iter()
+            .filter(|site| site.blocking.enabled)
+        {
+            problems.extend(site.blocking.schedule.problems(&site.url_pattern));
+        }
+        if self.dull_mode.enabled {
+            for (i, window) in self.dull_mode.windows.iter().enumerate() {
+                problems.extend(window.window_problems(&DullMode::window_name(i)));
+            }
+        }
+        if let BlockAction::Redirect(target) = &self.on_block {
+            problems.extend(target.problem().map(ConfigProblem::BadRedirectTarget));
+        }
+        if self.session_limit.minutes.is_some() && self.session_limit.min_gap == 0 {
+            problems.push(ConfigProblem::NoSessionGap);
+        }
+        problems.extend(self.notifications.problems());
+        problems
+    }
+}
+
+/// Every site that has a `sites::SiteAdapter`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Deserialize, Serialize)]
+pub enum Site {
+    YouTube,
+    Reddit,
+    Twitter,
+    Instagram,
+    Twitch,
+}
+
+impl Site {
+    pub const ALL: [Site; 5] = [
+        Site::YouTube,
+        Site::Reddit,
+        Site::Twitter,
+        Site::Instagram,
+        Site::Twitch,
+    ];
+
+    pub fn name(&self) -> &'static str {
+        match self {
+            Site::YouTube => "YouTube",
+            Site::Reddit => "Reddit",
+            Site::Twitter => "X/Twitter",
+            Site::Instagram => "Instagram",
+            Site::Twitch => "Twitch",
+        }
+    }
+}
+
+#[derive(Debug, Default, Clone, PartialEq, Eq, Deserialize, Serialize)]
+#[serde(default)]
+pub struct Schedule {
+    pub block_time_start: u32, // Time in minutes
+    pub block_time_end: u32,
+    pub active_days: u8, // bitmap of active days
+    pub daily_budget: Option<u32>,
+}
+
+#[derive(Debug, Default, Clone, PartialEq, Eq, Deserialize, Serialize)]
+#[serde(default)]
+pub struct SiteConfig {
+    pub enabled: bool,
+    pub schedule: Schedule,
+}
+
+/// A site the user added themselves, for when there's no built-in `SiteAdapter` for it.
+#[derive(Debug, Default, Clone, PartialEq, Eq, Deserialize, Serialize)]
+#[serde(default)]
+pub struct CustomSite {
+    /// WebExtension match pattern, e.g `*://*.example.com/*`. Also identifies the site in the usage
+    /// stats.
+    pub url_pattern: String,
+    /// What to do with the elements matching these while blocked.
+    pub selectors: Vec<String>,
+    pub selector_action: SelectorAction,
+    pub track_time: bool,
+    pub blocking: SiteConfig,
+}
+
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Deserialize, Serialize)]
+pub enum SelectorAction {
+    #[default]
+    Hide,
+    /// Swap the contents out for the block message, like the built-in sites do.
+    Replace,
+}
+
+/// Desktop notifications ahead of getting blocked, so that it doesn't come out of nowhere.
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize, Serialize)]
+#[serde(default)]
+pub struct NotificationSettings {
+    pub before_block_window: bool,
+    pub before_budget_runs_out: bool,
+    /// How many minutes in advance to warn. Each one gets its own notification.
+    pub lead_times: Vec<u32>,
+}
+
+impl NotificationSettings {
+    /// Part of `Config::problems()`, split out for showing next to the lead times input.
+    pub fn problems(&self) -> Vec<ConfigProblem> {
+        if !self.before_block_window && !self.before_budget_runs_out {
+            return Vec::new();
+        }
+        if self.lead_times.is_empty() {
+            return vec![ConfigProblem::NoLeadTimes];
+        }
+        self.lead_times
+            .iter()
+            .filter(|lead| !(1..=MAX_LEAD_TIME).contains(lead))
+            .map(|&lead| ConfigProblem::LeadTimeOutOfRange { lead })
+            .collect()
+    }
+}
+
+impl Default for NotificationSettings {
+    fn default() -> Self {
+        Self {
+            before_block_window: true,
+            before_budget_runs_out: true,
+            lead_times: vec![10, 2],
+        }
+    }
+}
+
+/// See `session`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Deserialize, Serialize)]
+#[serde(default)]
+pub struct SessionLimit {
+    /// None = sessions can go on as long as they like.
+    pub minutes: Option<u32>,
+    /// Minutes of break before a new session can start. At least 1, see `Config::problems()`.
+    pub min_gap: u32,
+}
+
+impl Default for SessionLimit {
+    fn default() -> Self {
+        Self {
+            minutes: None,
+            min_gap: 15,
+        }
+    }
+}
+
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Deserialize, Serialize)]
+pub enum Theme {
+    /// Whatever the OS/browser prefers.
+    #[default]
+    System,
+    Light,
+    Dark,
+}
+
+impl Theme {
+    /// Works `System` out through `prefers-color-scheme`.
+    pub fn is_dark(&self) -> bool {
+        match self {
+            Theme::Light => false,
+            Theme::Dark => true,
+            Theme::System => web_sys::window()
+                .and_then(|window| window.match_media("(prefers-color-scheme: dark)").ok())
+                .flatten()
+                .is_some_and(|query| query.matches()),
+        }
+    }
+}
+
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Deserialize, Serialize)]
+pub enum Language {
+    /// Whatever the browser's set to, falling back to English.
+    #[default]
+    Auto,
+    English,
+    Hindi,
+}
+
+/// When (and how) the weekly report (see `report`) comes up.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Deserialize, Serialize)]
+#[serde(default)]
+pub struct WeeklyReport {
+    pub delivery: ReportDelivery,
+    /// 0 = Monday, same as `active_days`.
+    pub weekday: u8,
+}
+
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Deserialize, Serialize)]
+pub enum ReportDelivery {
+    Off,
+    #[default]
+    Notification,
+    /// Open the report in a new tab, no questions asked.
+    OpenTab,
+}
+
+/// Small hurdles against getting hooked (see `friction`). All day, like Shorts mode.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Deserialize, Serialize)]
+#[serde(default)]
+pub struct FrictionSettings {
+    /// Keeps flipping YouTube's autoplay toggle back off.
+    pub disable_autoplay: bool,
+    pub pause_on_blur: bool,
+    /// Screenfuls of scrolling before asking whether to load more. None = scroll away.
+    pub scroll_page_limit: Option<u32>,
+}
+
+/// YouTube Shorts. These apply all the time, not just in the block window.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Deserialize, Serialize)]
+#[serde(default)]
+pub struct ShortsSettings {
+    /// The Shorts shelves on Home, search results and channel pages.
+    pub hide_shelves: bool,
+    pub hide_sidebar_entry: bool,
+    pub on_shorts_page: ShortsPageAction,
+}
+
+/// Short of blocking, makes YouTube look less appealing during its own window (`dull_mode`).
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize, Serialize)]
+#[serde(default)]
+pub struct DullMode {
+    pub enabled: bool,
+    /// It's on during any of these. Their daily budgets don't mean anything here.
+    pub windows: Vec<Schedule>,
+    pub grayscale: bool,
+    pub thumbnails: ThumbnailStyle,
+    pub hide_view_counts: bool,
+    /// No ALL CAPS titles.
+    pub tame_titles: bool,
+    /// From when there could only be one window. Only ever read, to carry it over (see
+    /// `carry_over_legacy()`).
+    #[serde(rename = "when", skip_serializing)]
+    pub legacy_when: Option<SiteConfig>,
+}
+
+impl Default for DullMode {
+    fn default() -> Self {
+        // Off until given a window, but everything's on once it is.
+        Self {
+            enabled: false,
+            windows: Vec::new(),
+            grayscale: true,
+            thumbnails: ThumbnailStyle::Blur,
+            hide_view_counts: true,
+            tame_titles: true,
+            legacy_when: None,
+        }
+    }
+}
+
+impl DullMode {
+    /// To tell the windows apart, in the problems and the settings. `i` counts from 0.
+    pub fn window_name(i: usize) -> String {
+        fill(t().dull_mode_window, &[("n", &(i + 1))])
+    }
+
+    fn carry_over_legacy(&mut self) {
+        if let Some(when) = self.legacy_when.take() {
+            if self.windows.is_empty() {
+                self.enabled = when.enabled;
+                self.windows.push(when.schedule);
+            }
+        }
+    }
+}
+
+/// Thumbnails go back to normal while hovered, either way.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Deserialize, Serialize)]
+pub enum ThumbnailStyle {
+    Normal,
+    #[default]
+    Blur,
+    Hide,
+}
+
+/// What happens on `/shorts/<id>`.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Deserialize, Serialize)]
+pub enum ShortsPageAction {
+    #[default]
+    Allow,
+    /// Open it in the normal player instead (`/watch?v=<id>`), where there's no swiping to the next
+    /// one.
+    WatchPage,
+    Block,
+}
+
+/// What the content script does with the YouTube homepage once we're inside the block window.
+#[derive(Debug, Default, Clone, PartialEq, Eq, Deserialize, Serialize)]
+pub enum BlockAction {
+    /// Replace the feed (and the watch page sidebar) with a blank message.
+    #[default]
+    Blank,
+    /// Navigate away from the homepage to somewhere less distracting.
+    Redirect(RedirectTarget),
+    /// Cover the whole page with an overlay.
+    Overlay,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize, Serialize)]
+pub enum RedirectTarget {
+    Subscriptions,
+    WatchLater,
+    Playlist(String), // Playlist ID, i.e the `list=` query param
+    Url(String),
+}
+
+/// Filters individual video cards (homepage feed, watch page sidebar). Unlike the block window,
+/// this is always on.
+#[derive(Debug, Default, Clone, PartialEq, Eq, Deserialize, Serialize)]
+#[serde(default)]
+pub struct FeedFilter {
+    /// If non-empty, only videos matching at least one of these rules are shown.
+    pub allow: FilterRules,
+    /// Videos matching any of these rules are hidden. Wins over `allow`.
+    pub deny: FilterRules,
+}
+
+#[derive(Debug, Default, Clone, PartialEq, Eq, Deserialize, Serialize)]
+#[serde(default)]
+pub struct FilterRules {
+    pub keywords: Vec<String>, // Case-insensitive substring match on the title
+    pub regexes: Vec<String>,  // JS RegExp source, matched against the title
+    pub channels: Vec<String>, // Channel name or @handle
+}
+
+impl FilterRules {
+    pub fn is_empty(&self) -> bool {
+        self.keywords.is_empty() && self.regexes.is_empty() && self.channels.is_empty()
     }
+}
 
+impl FeedFilter {
+    pub fn is_empty(&self) -> bool {
+        self.allow.is_empty() && self.deny.is_empty()
+    }
+}
+
+impl RedirectTarget {
+    /// None if there's nowhere to go (see `problem()`), in which case it's best to block like
+    /// `BlockAction::Blank` does instead.
+    pub fn url(&self) -> Option<String> {
+        if self.problem().is_some() {
+            return None;
+        }
+        let url = match self {
+            RedirectTarget::Subscriptions => {
+                "https://www.youtube.com/feed/subscriptions".to_owned()
+            }
+            RedirectTarget::WatchLater => "https://www.youtube.com/playlist?list=WL".to_owned(),
+            RedirectTarget::Playlist(id) => {
+                format!("https://www.youtube.com/playlist?list={}", id.trim())
+            }
+            RedirectTarget::Url(url) => url.trim().to_owned(),
+        };
+        Some(url)
+    }
+
+    pub fn problem(&self) -> Option<RedirectProblem> {
+        match self {
+            RedirectTarget::Subscriptions | RedirectTarget::WatchLater => None,
+            RedirectTarget::Playlist(id) | RedirectTarget::Url(id) if id.trim().is_empty() => {
+                Some(RedirectProblem::Missing)
+            }
+            RedirectTarget::Playlist(id)
+                if !id
+                    .trim()
+                    .chars()
+                    .all(|c| c.is_ascii_alphanumeric() || c == '-' || c == '_') =>
+            {
+                Some(RedirectProblem::BadPlaylistId)
+            }
+            RedirectTarget::Playlist(_) => None,
+            RedirectTarget::Url(url) => url_problem(url),
+        }
+    }
+}
+
+// By hand rather than with `web_sys::Url`, which would need a browser to run the tests in.
+fn url_problem(url: &str) -> Option<RedirectProblem> {
+    let url = url.trim().to_ascii_lowercase();
+    let Some(rest) = url
+        .strip_prefix("https://")
+        .or_else(|| url.strip_prefix("http://"))
+    else {
+        return Some(RedirectProblem::NotHttp);
+    };
+    let (authority, path) = rest.split_at(rest.find(['/', '?', '#']).unwrap_or(rest.len()));
+    // Without any user:password@ in front, or :port after.
+    let host = authority.rsplit('@').next().unwrap_or_default();
+    let host = host.split(':').next().unwrap_or_default();
+    if host.is_empty() {
+        return Some(RedirectProblem::NotHttp);
+    }
+
+    let path = path.split(['?', '#']).next().unwrap_or_default();
+    let is_youtube = host == "youtube.com" || host.ends_with(".youtube.com");
+    if is_youtube && (path.is_empty() || path == "/") {
+        return Some(RedirectProblem::Loop);
+    }
+    None
+}
+
+impl Config {
     pub async fn flush_config(&self) -> Result<(), StorageError> {
         update_storage(|storage| {
             storage.user_config = self.clone();
@@ -26,19 +607,185 @@ impl Config {
     }
 }
 
+/// Inverse of `day_key()`, at midnight local time.
+pub fn date_from_key(key: &str) -> Option<js_sys::Date> {
+    let mut parts = key.splitn(3, '-');
+    let year = parts.next()?.parse().ok()?;
+    let month: i32 = parts.next()?.parse().ok()?;
+    let day = parts.next()?.parse().ok()?;
+    Some(js_sys::Date::new_with_year_month_day(year, month - 1, day))
+}
+
+/// Key for `Usage::daily_usage`. "YYYY-MM-DD" in local time, so that keys sort chronologically.
+pub fn day_key(date: &js_sys::Date) -> String {
+    format!(
+        "{:04}-{:02}-{:02}",
+        date.get_full_year(),
+        date.get_month() + 1,
+        date.get_date()
+    )
+}
+
+/// The Monday of `date`'s week, `weeks_ago` weeks back, at midnight.
+pub fn monday_of(date: &js_sys::Date, weeks_ago: u32) -> js_sys::Date {
+    js_sys::Date::new_with_year_month_day(
+        date.get_full_year(),
+        date.get_month() as i32,
+        monday_day_of_month(date.get_date(), date.get_day(), weeks_ago),
+    )
+}
+
+/// Day of the month of that Monday, from `Date::get_date()` and `Date::get_day()`. Might end up <= 0
+/// (i.e last month), but `Date` handles rolling that over.
+fn monday_day_of_month(day_of_month: u32, weekday: u32, weeks_ago: u32) -> i32 {
+    // Sunday is 0, we want Monday to be.
+    let days_since_monday = (weekday + 6) % 7;
+    day_of_month as i32 - days_since_monday as i32 - 7 * weeks_ago as i32
+}
+
+/// Everything tracked about how the sites get used. Under a storage key of its own rather than in
+/// `Storage`: every open tab writes to it once a minute, and with one shared blob, a tab's write would
+/// undo the popup saving the settings in between (or the other way around).
+#[derive(Debug, Default, Clone, PartialEq, Eq, Deserialize, Serialize)]
+#[serde(default)]
+pub struct Usage {
+    /// Keyed by `day_key()`. Only goes back `USAGE_KEPT_WEEKS`.
+    pub daily_usage: BTreeMap<String, DayUsage>,
+    pub session: Session,
+    /// `Date.now()` of the last minute counted, for each thing tracked (see `count_minute()`).
+    pub last_counted: BTreeMap<String, u64>,
+}
+
+/// This week and the whole weeks before it, that `Usage::daily_usage` keeps. Covers the streak
+/// calendar, and as far back as the weekly report goes.
+pub const USAGE_KEPT_WEEKS: u32 = 12;
+
+impl Usage {
+    /// Whether a minute on `what` (say, a site's name) should be counted, with `now` being
+    /// `Date.now()`. Each tab ticks once a minute, so with a couple of them showing the same site (e.g
+    /// side by side), only the first tick in a minute counts.
+    pub fn count_minute(&mut self, what: &str, now: u64) -> bool {
+        const MINUTE_MS: u64 = 60 * 1000;
+        let last = self.last_counted.get(what).copied().unwrap_or(0);
+        if now.saturating_sub(last) < MINUTE_MS * 9 / 10 {
+            return false;
+        }
+        self.last_counted.insert(what.to_owned(), now);
+        true
+    }
+
+    /// Drops the days before `oldest_kept` (a `day_key()`).
+    pub fn prune(&mut self, oldest_kept: &str) {
+        self.daily_usage = self.daily_usage.split_off(oldest_kept);
+    }
+}
+
+/// `day_key()` of the Monday `USAGE_KEPT_WEEKS - 1` weeks before this one.
+fn oldest_kept_day() -> String {
+    day_key(&monday_of(&js_sys::Date::new_0(), USAGE_KEPT_WEEKS - 1))
+}
+
+#[derive(Debug, Default, Clone, PartialEq, Eq, Deserialize, Serialize)]
+#[serde(default)]
+pub struct DayUsage {
+    pub minutes: u32, // On YouTube
+    /// Keyed by video ID. Minutes spent anywhere other than a watch page only count towards `minutes`.
+    pub videos: BTreeMap<String, VideoUsage>,
+    /// Minutes spent on every other site.
+    pub sites: BTreeMap<Site, u32>,
+    /// Keyed by `CustomSite::url_pattern`. Only for the ones with `track_time` on.
+    pub custom_sites: BTreeMap<String, u32>,
+    /// Times the session limit got extended (see `session`).
+    #[serde(alias = "unlocks")]
+    pub session_extensions: u32,
+    /// Minutes on YouTube by hour of the day, index 0 being midnight to 1am. Empty for days from
+    /// before this was tracked, otherwise always 24 long.
+    pub hourly: Vec<u32>,
+    /// Everything that got blocked, counted once per page view. See `blocks()` for the total.
+    pub blocked: BTreeMap<BlockTarget, u32>,
+    /// From before `blocked`, when only the blocked page loads got counted. Only ever read, for
+    /// `blocks()` on the days that don't have anything else.
+    #[serde(rename = "blocked_attempts", skip_serializing)]
+    pub legacy_blocked_attempts: u32,
+}
+
+/// What a block was for, as counted in `DayUsage::blocked`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Deserialize, Serialize)]
+pub enum BlockTarget {
+    /// A site's homepage feed, however it got blocked (blanked out, redirected, covered).
+    Home,
+    /// The watch page sidebar, or whatever else gets blanked out off the homepage.
+    Sidebar,
+    /// A watch page of a channel that isn't on `Config::allowed_channels`.
+    Channel,
+    ShortsShelves,
+    ShortsSidebarEntry,
+    ShortsPage,
+    CustomSite,
+}
+
+impl BlockTarget {
+    pub fn label(&self) -> &'static str {
+        match self {
+            BlockTarget::Home => t().target_home,
+            BlockTarget::Sidebar => t().target_sidebar,
+            BlockTarget::Channel => t().target_channel,
+            BlockTarget::ShortsShelves => t().target_shorts_shelves,
+            BlockTarget::ShortsSidebarEntry => t().target_shorts_sidebar_entry,
+            BlockTarget::ShortsPage => t().target_shorts_page,
+            BlockTarget::CustomSite => t().target_custom_site,
+        }
+    }
+}
+
+impl DayUsage {
+    pub fn minutes_on(&self, site: Site) -> u32 {
+        match site {
+            Site::YouTube => self.minutes,
+            _ => self.sites.get(&site).copied().unwrap_or(0),
+        }
+    }
+
+    /// How many times anything got blocked, whatever it was.
+    pub fn blocks(&self) -> u32 {
+        if self.blocked.is_empty() {
+            self.legacy_blocked_attempts
+        } else {
+            self.blocked.values().sum()
+        }
+    }
+}
+
+#[derive(Debug, Default, Clone, PartialEq, Eq, Deserialize, Serialize)]
+#[serde(default)]
+pub struct VideoUsage {
+    pub title: String,
+    pub channel: String,
+    pub minutes: u32,
+}
+
 pub use storage_types::Storage;
 
 // ----------------------------------------------------------------------------------
 mod storage_types {
     use super::*;
+    use crate::selector_pack::SelectorPack;
     use serde::{Deserialize, Serialize};
     use serde_wasm_bindgen as swb;
     use wasm_bindgen::JsValue;
 
     #[derive(Debug, Default, Clone, PartialEq, Eq, Deserialize, Serialize)]
+    #[serde(default)]
     pub struct Storage {
         pub user_config: Config,
-        pub total_usage: u32,
+        /// The last pack loaded from a file/URL. Only used while it's newer than the bundled one.
+        pub selector_pack: Option<SelectorPack>,
+        /// `day_key()` of the last day the weekly report came up on, so that it only does once.
+        pub last_report: String,
+        /// From before `Usage` had a key of its own. Only ever read, to carry it over (see
+        /// `get_usage()`).
+        #[serde(rename = "daily_usage", skip_serializing)]
+        pub legacy_daily_usage: BTreeMap<String, DayUsage>,
     }
 
     impl TryFrom<StorageSerdeWrapper> for Storage {
@@ -46,7 +793,11 @@ mod storage_types {
 
         fn try_from(value: StorageSerdeWrapper) -> Result<Self, Self::Error> {
             match value {
-                StorageSerdeWrapper::Storage(storage) => Ok(storage),
+                StorageSerdeWrapper::Storage(storage) => {
+                    let mut storage = *storage;
+                    storage.user_config.dull_mode.carry_over_legacy();
+                    Ok(storage)
+                }
                 StorageSerdeWrapper::EmptyStorage(_) => Err(StorageError::EmptyStorage),
             }
         }
@@ -68,7 +819,7 @@ mod storage_types {
     #[derive(Serialize, Deserialize)]
     pub enum StorageSerdeWrapper {
         #[serde(rename = "config")]
-        Storage(Storage),
+        Storage(Box<Storage>),
         #[serde(untagged)]
         EmptyStorage(EmptyStruct),
     }
@@ -84,8 +835,23 @@ mod storage_types {
 }
 // ---------------------------------------------------------------------------
 
+// `browser.storage` (or `chrome.storage`) is only defined inside extension contexts. Touching it anywhere else (say, a
+// normal webpage that somehow loaded our .wasm) throws, so check for it first and give a proper error instead. Checking
+// for the global alone isn't enough: Chromium defines a bare `chrome` on ordinary webpages too.
+fn local_storage() -> Result<StorageArea, StorageError> {
+    let has_storage = js_sys::Reflect::get(&js_sys::global(), &JsValue::from_str(BROWSER_GLOBAL))
+        .ok()
+        .filter(|global| global.is_object())
+        .and_then(|global| js_sys::Reflect::get(&global, &JsValue::from_str("storage")).ok())
+        .is_some_and(|storage| storage.is_object());
+    if !has_storage {
+        return Err(StorageError::StorageNotFound);
+    }
+    Ok(browser().storage().local())
+}
+
 pub async fn get_storage() -> Result<Storage, StorageError> {
-    let storage = browser().storage().local();
+    let storage = local_storage()?;
 
     let config_jsval = storage
         .get(&JsValue::from_str("config"))
@@ -94,14 +860,18 @@ pub async fn get_storage() -> Result<Storage, StorageError> {
 
     let config_wrapper = StorageSerdeWrapper::try_from(config_jsval)?;
 
-    Ok(Storage::try_from(config_wrapper)?)
+    Storage::try_from(config_wrapper)
 }
 
 pub async fn set_storage(storage: Storage) -> Result<(), StorageError> {
-    let browser_storage = browser().storage().local();
+    let browser_storage = local_storage()?;
 
     console_log!("[DEBUG]: browser_storage retrieved!");
-    let config_jsval = swb::to_value(&StorageSerdeWrapper::Storage(storage)).expect(
+    // JSON-compatible, because by default maps get serialized into JS `Map`s, which the storage API
+    // can't store.
+    let config_jsval = StorageSerdeWrapper::Storage(Box::new(storage))
+        .serialize(&swb::Serializer::json_compatible())
+        .expect(
         "All types should've been correct because Rust (and its cool static type system(TM)) :)",
     );
 
@@ -116,7 +886,7 @@ pub async fn set_storage(storage: Storage) -> Result<(), StorageError> {
 }
 
 pub async fn remove_storage() -> Result<(), StorageError> {
-    let storage = browser().storage().local();
+    let storage = local_storage()?;
     let _ = storage
         .remove(&JsValue::from_str("config"))
         .await
@@ -139,6 +909,75 @@ pub async fn update_storage(f: impl FnOnce(&mut Storage)) -> Result<Storage, Sto
     Ok(config)
 }
 
+const USAGE_KEY: &str = "usage";
+
+/// Usage is tracked whether or not there's a config, so there's no `StorageError::EmptyStorage` here.
+pub async fn get_usage() -> Result<Usage, StorageError> {
+    match get_value(USAGE_KEY).await? {
+        Some(usage) => Ok(usage),
+        // Either nothing's been tracked yet, or it's all still in the config.
+        None => {
+            let legacy_daily_usage = get_storage()
+                .await
+                .map(|storage| storage.legacy_daily_usage)
+                .unwrap_or_default();
+            Ok(Usage {
+                daily_usage: legacy_daily_usage,
+                ..Default::default()
+            })
+        }
+    }
+}
+
+pub async fn update_usage(f: impl FnOnce(&mut Usage)) -> Result<Usage, StorageError> {
+    let mut usage = get_usage().await.or_else(|err| {
+        if err == StorageError::CorruptedConfig {
+            console_log!("The usage is corrupted, starting over");
+            Ok(Default::default())
+        } else {
+            Err(err)
+        }
+    })?;
+
+    f(&mut usage);
+    usage.prune(&oldest_kept_day());
+    set_value(USAGE_KEY, &usage).await?;
+
+    Ok(usage)
+}
+
+/// Reads whatever's stored under `key` in the local storage, next to the config. None = nothing
+/// stored there yet.
+pub async fn get_value<T: DeserializeOwned>(key: &str) -> Result<Option<T>, StorageError> {
+    let storage = local_storage()?;
+    let stored = storage
+        .get(&JsValue::from_str(key))
+        .await
+        .map_err(|_| StorageError::WontAllowStorage)?;
+    let value =
+        js_sys::Reflect::get(&stored, &JsValue::from_str(key)).unwrap_or(JsValue::UNDEFINED);
+    if value.is_undefined() {
+        return Ok(None);
+    }
+    swb::from_value(value)
+        .map(Some)
+        .map_err(|_| StorageError::CorruptedConfig)
+}
+
+pub async fn set_value<T: Serialize>(key: &str, value: &T) -> Result<(), StorageError> {
+    // JSON-compatible for the same reason as in `set_storage()`.
+    let value = value.serialize(&swb::Serializer::json_compatible()).expect(
+        "All types should've been correct because Rust (and its cool static type system(TM)) :)",
+    );
+    let obj = js_sys::Object::new();
+    let _ = js_sys::Reflect::set(&obj, &JsValue::from_str(key), &value);
+    local_storage()?
+        .set(&obj)
+        .await
+        .map_err(|_| StorageError::WontAllowStorage)?;
+    Ok(())
+}
+
 #[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord)]
 pub enum StorageError {
     WontAllowStorage,
@@ -149,14 +988,224 @@ pub enum StorageError {
 
 impl std::fmt::Display for StorageError {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-        write!(f, "StorageError: ")?;
         let msg = match self {
-            StorageError::WontAllowStorage => "The user has not allowed storage",
-            StorageError::EmptyStorage => "The storage is empty",
-            StorageError::StorageNotFound => "The window context/storage context was not found",
-            StorageError::CorruptedConfig => "The config is corrupted",
+            StorageError::WontAllowStorage => t().storage_error_not_allowed,
+            StorageError::EmptyStorage => t().storage_error_empty,
+            StorageError::StorageNotFound => t().storage_error_not_found,
+            StorageError::CorruptedConfig => t().storage_error_corrupted,
         };
-        writeln!(f, "{msg}")
+        f.write_str(msg)
     }
 }
 impl Error for StorageError {}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn valid_config() -> Config {
+        Config {
+            block_time_start: 9 * 60,
+            block_time_end: 17 * 60,
+            active_days: 0b111_1111,
+            ..Default::default()
+        }
+    }
+
+    fn schedule(start: u32, end: u32, active_days: u8, daily_budget: Option<u32>) -> Schedule {
+        Schedule {
+            block_time_start: start,
+            block_time_end: end,
+            active_days,
+            daily_budget,
+        }
+    }
+
+    #[test]
+    fn valid_config_has_no_problems() {
+        assert_eq!(valid_config().problems(), vec![]);
+    }
+
+    #[test]
+    fn youtube_schedule_problems() {
+        let config = Config {
+            active_days: 0,
+            block_time_end: 9 * 60,
+            daily_budget: Some(0),
+            ..valid_config()
+        };
+        let what = "YouTube".to_owned();
+        assert_eq!(
+            config.problems(),
+            vec![
+                ConfigProblem::NoActiveDays { what: what.clone() },
+                ConfigProblem::EmptyWindow { what: what.clone() },
+                ConfigProblem::BudgetOutOfRange { what, budget: 0 },
+            ]
+        );
+
+        for budget in [1, MAX_DAILY_BUDGET] {
+            let config = Config {
+                daily_budget: Some(budget),
+                ..valid_config()
+            };
+            assert_eq!(config.problems(), vec![]);
+        }
+        let config = Config {
+            daily_budget: Some(MAX_DAILY_BUDGET + 1),
+            ..valid_config()
+        };
+        assert_eq!(config.problems().len(), 1);
+    }
+
+    #[test]
+    fn disabled_schedules_dont_count() {
+        let mut config = valid_config();
+        config.sites.insert(
+            Site::Reddit,
+            SiteConfig {
+                enabled: false,
+                schedule: schedule(0, 0, 0, Some(0)),
+            },
+        );
+        config.custom_sites.push(CustomSite {
+            url_pattern: "*://example.com/*".to_owned(),
+            blocking: SiteConfig {
+                enabled: false,
+                schedule: Schedule::default(),
+            },
+            ..Default::default()
+        });
+        config.dull_mode.windows.push(Schedule::default());
+        assert_eq!(config.problems(), vec![]);
+
+        config.sites.get_mut(&Site::Reddit).unwrap().enabled = true;
+        config.custom_sites[0].blocking.enabled = true;
+        assert_eq!(config.problems().len(), 3 + 2);
+    }
+
+    #[test]
+    fn dull_mode_budget_isnt_checked() {
+        let mut config = valid_config();
+        config.dull_mode.enabled = true;
+        config
+            .dull_mode
+            .windows
+            .push(schedule(20 * 60, 23 * 60, 0b111_1111, Some(0)));
+        config.dull_mode.windows.push(schedule(60, 60, 1, None));
+        assert_eq!(
+            config.problems(),
+            vec![ConfigProblem::EmptyWindow {
+                what: DullMode::window_name(1)
+            }]
+        );
+    }
+
+    #[test]
+    fn redirect_targets() {
+        let problem = |target: RedirectTarget| {
+            let config = Config {
+                on_block: BlockAction::Redirect(target),
+                ..valid_config()
+            };
+            config.problems()
+        };
+        let bad = |problem| vec![ConfigProblem::BadRedirectTarget(problem)];
+
+        assert_eq!(problem(RedirectTarget::Subscriptions), vec![]);
+        assert_eq!(
+            problem(RedirectTarget::Playlist("PLabc-_123".to_owned())),
+            vec![]
+        );
+        assert_eq!(
+            problem(RedirectTarget::Playlist(" ".to_owned())),
+            bad(RedirectProblem::Missing)
+        );
+        assert_eq!(
+            problem(RedirectTarget::Playlist("abc&x=1".to_owned())),
+            bad(RedirectProblem::BadPlaylistId)
+        );
+
+        for url in [
+            "https://example.com",
+            "http://youtube.com/feed/subscriptions",
+        ] {
+            assert_eq!(problem(RedirectTarget::Url(url.to_owned())), vec![]);
+        }
+        assert_eq!(
+            problem(RedirectTarget::Url(String::new())),
+            bad(RedirectProblem::Missing)
+        );
+        for url in [
+            "javascript:alert(1)",
+            "example.com",
+            "https://",
+            "ftp://example.com",
+        ] {
+            assert_eq!(
+                problem(RedirectTarget::Url(url.to_owned())),
+                bad(RedirectProblem::NotHttp),
+                "{url}"
+            );
+        }
+        for url in [
+            "https://www.youtube.com",
+            "https://youtube.com/",
+            "HTTPS://M.YOUTUBE.COM/?feature=x",
+            "https://user@youtube.com:443/#top",
+        ] {
+            assert_eq!(
+                problem(RedirectTarget::Url(url.to_owned())),
+                bad(RedirectProblem::Loop),
+                "{url}"
+            );
+        }
+    }
+
+    #[test]
+    fn session_gap() {
+        let mut config = valid_config();
+        config.session_limit.min_gap = 0;
+        // Doesn't matter without a limit.
+        assert_eq!(config.problems(), vec![]);
+        config.session_limit.minutes = Some(30);
+        assert_eq!(config.problems(), vec![ConfigProblem::NoSessionGap]);
+        config.session_limit.min_gap = 1;
+        assert_eq!(config.problems(), vec![]);
+    }
+
+    #[test]
+    fn monday_of_the_week() {
+        // Monday the 12th, Wednesday the 14th and Sunday the 18th (October 2026).
+        for (day_of_month, weekday) in [(12, 1), (14, 3), (18, 0)] {
+            assert_eq!(monday_day_of_month(day_of_month, weekday, 0), 12);
+        }
+        assert_eq!(monday_day_of_month(19, 1, 1), 12);
+        assert_eq!(monday_day_of_month(26, 1, 2), 12);
+        // Into the month before, e.g Sunday the 1st of March 2026 is in the week of February 23rd.
+        assert_eq!(monday_day_of_month(1, 0, 0), -5);
+        assert_eq!(monday_day_of_month(3, 2, 11), -75);
+    }
+
+    #[test]
+    fn lead_times() {
+        let mut config = valid_config();
+        config.notifications.lead_times = vec![];
+        assert_eq!(config.problems(), vec![ConfigProblem::NoLeadTimes]);
+
+        config.notifications.lead_times = vec![0, 1, MAX_LEAD_TIME, MAX_LEAD_TIME + 1];
+        assert_eq!(
+            config.problems(),
+            vec![
+                ConfigProblem::LeadTimeOutOfRange { lead: 0 },
+                ConfigProblem::LeadTimeOutOfRange {
+                    lead: MAX_LEAD_TIME + 1
+                },
+            ]
+        );
+
+        config.notifications.before_block_window = false;
+        config.notifications.before_budget_runs_out = false;
+        assert_eq!(config.problems(), vec![]);
+    }
+}
@@ -1,5 +1,15 @@
-use crate::config::{self, Config, StorageError};
-use crate::console_log;
+use crate::bindings::browser;
+use crate::config::{
+    self, BlockAction, BlockTarget, Config, SelectorAction, Site, StorageError, Theme,
+};
+use crate::messages::{Message, SelfTestReport};
+use crate::sites::{self, MediaInfo, SiteAdapter};
+use crate::{
+    console_log, custom_sites, dull_mode, feed_filter, friction, i18n, schedule, selector_pack,
+    session, shorts,
+};
+use std::cell::{Cell, RefCell};
+use std::collections::HashSet;
 use wasm_bindgen::prelude::*;
 use wasm_bindgen_futures::spawn_local;
 
@@ -8,7 +18,12 @@ pub async fn touch_grass() {
     console_error_panic_hook::set_once();
 
     let window = web_sys::window().expect("no global `window` exists");
-    let document = window.document().expect("should have a document on window");
+    let adapter = current_adapter(&window);
+
+    // Before anything can bail out below: the user might set things up after this page loaded.
+    if adapter.is_some() {
+        listen_for_messages();
+    }
 
     let storage = match config::get_storage().await {
         Ok(storage) => storage,
@@ -21,49 +36,242 @@ pub async fn touch_grass() {
             return;
         }
     };
-    console_log!("Storage: {storage:?}");
+    let config = &storage.user_config;
+    console_log!("Config: {config:?}");
+    let usage = config::get_usage().await.unwrap_or_else(|e| {
+        console_log!("Couldn't get the usage, going by none: {e}");
+        Default::default()
+    });
+    THEME.set(config.theme);
+    i18n::set_language(config.language);
+    selector_pack::activate(storage.selector_pack.as_ref());
+
+    // The user's own sites can overlap with the built-in ones, hence before the bail out below.
+    custom_sites::start(&window, config, &usage);
+    let Some(adapter) = adapter else {
+        console_log!("Not a site we know of, returning..");
+        return;
+    };
+
+    // None of filtering, the friction features, Shorts mode and dull mode are tied to the block
+    // window, so kick them off before we bail out below.
+    feed_filter::start_filtering(&window, adapter, &config.feed_filter);
+    friction::start(&window, adapter, config.friction);
+    if adapter.site() == Site::YouTube {
+        shorts::start(&window, adapter, config.shorts);
+        dull_mode::start(&window, &config.dull_mode);
+        session::enforce(&window, adapter, config.session_limit, &usage.session);
+    }
 
-    let curr_time = js_sys::Date::new_0();
-    if !(within_active_time_window(&storage.user_config, &curr_time)
-        && within_active_day_window(&storage.user_config, &curr_time))
-    {
+    // Usage is tracked all day, not just inside the block window. Otherwise the stats would only
+    // ever show the time spent on a blocked YouTube.
+    record_watch_time(&window, adapter);
+
+    let block_state = schedule::block_state(config, &usage, adapter.site());
+    if !block_state.is_blocked() {
         console_log!("Not within the active window, returning..");
         return;
     }
+    console_log!("Blocking because: {block_state:?}");
 
-    record_watch_time(&window)
-        .await
-        .expect("Couldn't start recording watch statistics");
+    apply_block(&window, adapter, config);
+}
+
+fn current_adapter(window: &web_sys::Window) -> Option<&'static dyn SiteAdapter> {
+    sites::adapter_for_host(&window.location().hostname().ok()?)
+}
+
+fn current_path(window: &web_sys::Window) -> String {
+    window.location().pathname().unwrap_or_default()
+}
+
+thread_local! {
+    // Whether the block is currently applied to this page.
+    static IS_BLOCKED: Cell<bool> = const { Cell::new(false) };
+    // setInterval handle of `enforce_channel_allowlist`, so that it can be stopped on unblock.
+    static ALLOWLIST_INTERVAL: Cell<Option<i32>> = const { Cell::new(None) };
+    // `Config::theme` as of page load, for the block screen.
+    static THEME: Cell<Theme> = const { Cell::new(Theme::System) };
+    // The page (path + query) that `COUNTED_BLOCKS` is for. Navigating elsewhere starts over.
+    static COUNTED_PAGE: RefCell<String> = const { RefCell::new(String::new()) };
+    // What's already been counted as blocked on that page.
+    static COUNTED_BLOCKS: RefCell<HashSet<BlockTarget>> = RefCell::default();
+}
+
+/// Adds one to today's `DayUsage::blocked` for `target`, unless it's already been counted for the
+/// current page. Most of the blocking runs every second, and YouTube re-renders whatever got
+/// removed, so without that one page view would count dozens of times.
+pub fn count_block(target: BlockTarget) {
+    if is_counted(target) {
+        return;
+    }
+    spawn_local(record_block(target));
+}
+
+/// Whether `target` has already been counted on the current page, marking it as counted if not.
+fn is_counted(target: BlockTarget) -> bool {
+    let Some(window) = web_sys::window() else {
+        return true;
+    };
+    let location = window.location();
+    let page = location.pathname().unwrap_or_default() + &location.search().unwrap_or_default();
+    if COUNTED_PAGE.with_borrow(|current| *current != page) {
+        COUNTED_PAGE.set(page);
+        COUNTED_BLOCKS.with_borrow_mut(HashSet::clear);
+    }
+    !COUNTED_BLOCKS.with_borrow_mut(|counted| counted.insert(target))
+}
+
+async fn record_block(target: BlockTarget) {
+    let today = config::day_key(&js_sys::Date::new_0());
+    let result = config::update_usage(|usage| {
+        let day = usage.daily_usage.entry(today).or_default();
+        *day.blocked.entry(target).or_default() += 1;
+    })
+    .await;
+    if let Err(e) = result {
+        console_log!("Couldn't count the block of {target:?}: {e}");
+    }
+}
+
+fn apply_block(window: &web_sys::Window, adapter: &'static dyn SiteAdapter, config: &Config) {
+    if IS_BLOCKED.replace(true) {
+        return;
+    }
+    let document = window.document().expect("should have a document on window");
+    let path = current_path(window);
 
-    remove_distractions(&document);
+    // The on-block action is only about the homepage. Watch pages would otherwise become unusable,
+    // which isn't the point; their sidebar still gets blanked out like usual.
+    if adapter.is_homepage(&path) {
+        let removed = match &config.on_block {
+            BlockAction::Blank => remove_built_in_distractions(&document, adapter, &path),
+            // The redirect targets are YouTube pages (bar the custom URL), which would be a weird
+            // place for, say, Reddit to send you to. No `url()` means there's nowhere sensible to go,
+            // and the settings form shouldn't have let that through anyway.
+            BlockAction::Redirect(target) if adapter.site() == Site::YouTube => {
+                match target.url() {
+                    Some(url) => {
+                        // Counted here, before leaving, rather than below: the page unloading would
+                        // take the storage write down with it.
+                        let window = window.clone();
+                        let counted = is_counted(BlockTarget::Home);
+                        spawn_local(async move {
+                            if !counted {
+                                record_block(BlockTarget::Home).await;
+                            }
+                            redirect_homepage(&window, &url);
+                        });
+                        0
+                    }
+                    None => remove_built_in_distractions(&document, adapter, &path),
+                }
+            }
+            BlockAction::Redirect(_) => remove_built_in_distractions(&document, adapter, &path),
+            BlockAction::Overlay => {
+                show_block_overlay(&document, HOMEPAGE_OVERLAY_ID);
+                1
+            }
+        };
+        if removed > 0 {
+            count_block(BlockTarget::Home);
+        }
+    } else if remove_built_in_distractions(&document, adapter, &path) > 0 {
+        count_block(BlockTarget::Sidebar);
+    }
+
+    if !config.allowed_channels.is_empty() {
+        enforce_channel_allowlist(window, adapter, &config.allowed_channels);
+    }
 }
 
-fn within_active_time_window(config: &Config, curr_time: &js_sys::Date) -> bool {
-    let curr_time = curr_time.get_hours() * 60 + curr_time.get_minutes();
-    console_log!("Curr time: {curr_time}");
-    // CAREFUL! If start_time > end_time (eg: start_time=10:00PM, end_time=6:00AM)
-    //  then it isn't a simple range-check.
-    //  Either I:
-    //  - handle both cases separately, i.e case start_time > end_time: if (end_time..24*60) || (0..start_time) and
-    //    case start_time <= end_time: another if block for normal case where start_time <= end_time.
-    //  - handle it in one go: chec if time is NOT in the range (smaller_time..larger_time).
-    //  For some reason, I like the second one better, as it can be composed without multiple if-else's,
-    //  as done below.
-    let is_normal_check = config.block_time_start <= config.block_time_end;
-    let early_hr = config.block_time_start.min(config.block_time_end);
-    let late_hr = config.block_time_start.max(config.block_time_end);
-    let outside_time_window = is_normal_check ^ (early_hr..late_hr).contains(&curr_time);
+fn lift_block(window: &web_sys::Window, adapter: &dyn SiteAdapter) {
+    if !IS_BLOCKED.replace(false) {
+        return;
+    }
+    let document = window.document().expect("should have a document on window");
 
-    return !outside_time_window;
+    if let Some(interval) = ALLOWLIST_INTERVAL.take() {
+        window.clear_interval_with_handle(interval);
+    }
+    remove_block_overlay(&document, HOMEPAGE_OVERLAY_ID);
+    remove_block_overlay(&document, CHANNEL_OVERLAY_ID);
+
+    // We've thrown away the feed's contents, and there's no getting them back without a reload. Not
+    // doing the same for watch pages though; interrupting the video would be worse than a blanked out
+    // sidebar, and it comes back on the next page load anyways.
+    if adapter.is_homepage(&current_path(window)) {
+        let _ = window.location().reload();
+    }
 }
 
-fn within_active_day_window(config: &Config, curr_time: &js_sys::Date) -> bool {
-    // Sunday is zero. Fuck that, why does it start with the weekend?
-    let curr_day = curr_time.get_day();
-    const NUM_DAYS_IN_WEEK: u32 = 7;
-    let curr_day = (curr_day + NUM_DAYS_IN_WEEK - 1) % NUM_DAYS_IN_WEEK;
+/// The background page tells us whenever we cross into/out of being blocked, so that an already
+/// open YouTube tab doesn't have to be reloaded for the schedule to kick in. The popup also asks us
+/// for selector self-tests.
+fn listen_for_messages() {
+    let on_message = Closure::<dyn Fn(JsValue, JsValue, js_sys::Function)>::new(
+        |message: JsValue, _sender: JsValue, send_response: js_sys::Function| match Message::from_js(
+            message,
+        ) {
+            Some(Message::BlockStateChanged { blocked }) => on_block_state_changed(blocked),
+            Some(Message::RunSelfTest) => {
+                if let Some(report) = run_self_test() {
+                    let _ = send_response.call1(&JsValue::NULL, &report.to_js());
+                }
+            }
+            None => {}
+        },
+    );
 
-    (config.active_days & (1 << curr_day)) != 0
+    browser()
+        .runtime()
+        .on_message()
+        .add_listener(on_message.as_ref().unchecked_ref());
+    // Lives as long as the page does.
+    on_message.forget();
+}
+
+fn on_block_state_changed(blocked: bool) {
+    console_log!("Block state changed, blocked: {blocked}");
+    spawn_local(async move {
+        let window = web_sys::window().expect("no global `window` exists");
+        let Some(adapter) = current_adapter(&window) else {
+            return;
+        };
+        if !blocked {
+            lift_block(&window, adapter);
+            return;
+        }
+        // Re-read the config rather than holding on to the one from page load; it may have been
+        // changed since.
+        match config::get_storage().await {
+            Ok(storage) => apply_block(&window, adapter, &storage.user_config),
+            Err(e) => {
+                console_log!("Couldn't apply the block: {e}");
+            }
+        }
+    });
+}
+
+/// Checks the selectors meant for the current page against it. Only makes sense while it isn't
+/// blocked, since blocking takes the matched elements out of the page (and done right after it has
+/// loaded).
+fn run_self_test() -> Option<SelfTestReport> {
+    let window = web_sys::window()?;
+    let adapter = current_adapter(&window)?;
+    let document = window.document()?;
+
+    let mut tested = adapter.distraction_selectors(&current_path(&window));
+    tested.extend(adapter.card_selector());
+    let unmatched = selector_pack::self_test(&document, &tested);
+    console_log!("Self-test, selectors that matched nothing: {unmatched:?}");
+
+    Some(SelfTestReport {
+        url: window.location().href().unwrap_or_default(),
+        pack_version: selector_pack::with_active(|pack| pack.version),
+        tested,
+        unmatched,
+    })
 }
 
 /**
@@ -74,54 +282,352 @@ fn within_active_day_window(config: &Config, curr_time: &js_sys::Date) -> bool {
  * that is somehow called when the page is closed (maybe some "run_at" attribute in manifest.json).
  * The reason I've decided not to do that:
  * - I'd need to add yet another JS binding file because ManifestV2 can't directly
- * call into .wasm (afaik) and needs a glue JS file; which will then call into a disjointed Rust file,
- * which would need to be included in the module (for LSP to work).
- * but wouldn't actually be related to the functioning of the module. This incrementally
- * makes the project feel more confusing when files are grouped together not by functionality
- * but by nature of language.
+ *   call into .wasm (afaik) and needs a glue JS file; which will then call into a disjointed Rust file,
+ *   which would need to be included in the module (for LSP to work).
+ *   but wouldn't actually be related to the functioning of the module. This incrementally
+ *   makes the project feel more confusing when files are grouped together not by functionality
+ *   but by nature of language.
+ *
  * An alternative that I've not explored is if there's a way to do it at runtime (not describing it in manifest.json)
  * but rather programmtically via Rust code only. Then, I can bypass JS glue code.
  */
-async fn record_watch_time(document: &web_sys::Window) -> Result<(), StorageError> {
-    config::update_storage(|storage| {
-        storage.total_usage = 1;
-    })
-    .await?;
-
-    let closure = Closure::<dyn Fn()>::new(|| spawn_local(increment_total_usage()));
+fn record_watch_time(document: &web_sys::Window, adapter: &'static dyn SiteAdapter) {
+    let closure = Closure::<dyn Fn()>::new(move || spawn_local(increment_usage(adapter)));
 
     document
         .set_interval_with_callback_and_timeout_and_arguments_0(
             closure.as_ref().unchecked_ref(),
-            1 * 60 * 1000,
+            60 * 1000,
         )
         .expect("Failed to setInterval the total usage tracker.");
 
     // Leaking memory here is fine, because this closure is supposed to live until the end of the page
     // anyways, as it belongs into a setInterval function.
     closure.forget();
-    Ok(())
 }
 
-async fn increment_total_usage() {
+/// Whether time spent on the current page counts: only while it's actually on screen, not from a
+/// tab in the background.
+pub fn page_is_visible() -> bool {
+    web_sys::window()
+        .and_then(|window| window.document())
+        .is_some_and(|document| document.visibility_state() == web_sys::VisibilityState::Visible)
+}
+
+async fn increment_usage(adapter: &'static dyn SiteAdapter) {
+    if !page_is_visible() {
+        return;
+    }
+    let site = adapter.site();
+    let media = web_sys::window()
+        .and_then(|window| window.document())
+        .and_then(|document| adapter.current_media(&document));
+    let now = js_sys::Date::new_0();
+    let today = config::day_key(&now);
+    let hour = now.get_hours() as usize;
+    // Only read here. Whatever the popup saves in the meantime is left alone.
+    let session_limit = config::get_storage()
+        .await
+        .map(|storage| storage.user_config.session_limit)
+        .unwrap_or_default();
+
     // WHY .unwrap(): I already have meaningful messages for the errors that're going to be propagated.
     // No need to muddle it with a generic-ass message again.
-    config::update_storage(|storage| {
-        storage.total_usage += 1;
+    let usage = config::update_usage(|usage| {
+        if !usage.count_minute(site.name(), now.get_time() as u64) {
+            return;
+        }
+        let day = usage.daily_usage.entry(today).or_default();
+        if site != Site::YouTube {
+            *day.sites.entry(site).or_default() += 1;
+            return;
+        }
+
+        day.minutes += 1;
+        day.hourly.resize(24, 0);
+        day.hourly[hour] += 1;
+        usage.session.tick(&session_limit, session::now());
+        if let Some(MediaInfo {
+            id: Some(video_id),
+            title,
+            channel_name,
+            ..
+        }) = media
+        {
+            let video = day.videos.entry(video_id).or_default();
+            // Overwrite, in case the uploader renamed the video/channel since we last saw it.
+            video.title = title;
+            video.channel = channel_name.unwrap_or_default();
+            video.minutes += 1;
+        }
     })
     .await
     .unwrap();
+
+    if let (Some(window), Site::YouTube) = (web_sys::window(), site) {
+        session::enforce(&window, adapter, session_limit, &usage.session);
+    }
+}
+
+fn remove_built_in_distractions(
+    document: &web_sys::Document,
+    adapter: &dyn SiteAdapter,
+    path: &str,
+) -> u32 {
+    remove_distractions(
+        document,
+        &adapter.distraction_selectors(path),
+        SelectorAction::Replace,
+    )
+}
+
+/// Hides/blanks out everything matching `selectors`. Shared with the custom sites, whose selectors
+/// come from the user (so they may well be invalid; those get skipped).
+///
+/// Returns how many elements it took out, not counting the ones already taken out before.
+pub fn remove_distractions<S: AsRef<str>>(
+    document: &web_sys::Document,
+    selectors: &[S],
+    action: SelectorAction,
+) -> u32 {
+    let mut removed = 0;
+    let block_message = block_message();
+    for selector in selectors {
+        let Ok(elements) = document.query_selector_all(selector.as_ref()) else {
+            continue;
+        };
+        for i in 0..elements.length() {
+            let Some(el) = elements
+                .item(i)
+                .and_then(|el| el.dyn_into::<web_sys::HtmlElement>().ok())
+            else {
+                continue;
+            };
+            match action {
+                SelectorAction::Hide => {
+                    if !el.has_attribute(HIDDEN_ATTRIBUTE) {
+                        removed += 1;
+                    }
+                    let _ = el.style().set_property("display", "none");
+                    // So it can be told apart from stuff the page hid itself, when unhiding.
+                    let _ = el.set_attribute(HIDDEN_ATTRIBUTE, "");
+                }
+                // Checking first, since this runs every second for the custom sites. Resetting the
+                // contents every time would also reset any scrolling etc.
+                SelectorAction::Replace if el.inner_html() != block_message => {
+                    el.set_inner_html(&block_message);
+                    removed += 1;
+                }
+                SelectorAction::Replace => {}
+            }
+        }
+    }
+    removed
+}
+
+// The 🌱 is aria-hidden, else screen readers go "seedling" before the actual message.
+fn block_message() -> String {
+    format!(
+        "<h1><span aria-hidden=\"true\">🌱</span>\n{}</h1>",
+        i18n::t().block_message
+    )
+}
+
+pub const HIDDEN_ATTRIBUTE: &str = "data-touch-grass-hidden";
+
+fn redirect_homepage(window: &web_sys::Window, url: &str) {
+    console_log!("Redirecting homepage to {url}");
+    if let Err(e) = window.location().replace(url) {
+        console_log!("Couldn't redirect to {url}: {e:?}");
+    }
+}
+
+// Separate overlays so that the channel allowlist taking its overlay down doesn't also take down the
+// homepage's one.
+const HOMEPAGE_OVERLAY_ID: &str = "touch-grass-overlay";
+const CHANNEL_OVERLAY_ID: &str = "touch-grass-channel-overlay";
+// On every overlay, so they can be found without knowing all the IDs.
+const OVERLAY_ATTRIBUTE: &str = "data-touch-grass-overlay";
+// On whatever we made `inert` for an overlay, so that only those get un-inerted afterwards.
+const INERT_ATTRIBUTE: &str = "data-touch-grass-inert";
+
+pub fn show_block_overlay(document: &web_sys::Document, overlay_id: &str) {
+    if document.get_element_by_id(overlay_id).is_some() {
+        return;
+    }
+
+    let Some(body) = document.body() else {
+        console_log!("No <body> to put the overlay on");
+        return;
+    };
+    let overlay = document
+        .create_element("div")
+        .expect("Creating a div should never fail");
+    overlay.set_id(overlay_id);
+    let _ = overlay.set_attribute(OVERLAY_ATTRIBUTE, "");
+    let (background, color) = overlay_colors();
+    let _ = overlay.set_attribute(
+        "style",
+        &format!(
+            "position: fixed; inset: 0; z-index: 2147483647; display: flex; align-items: center; \
+             justify-content: center; background: {background}; color: {color}; font-size: 3rem;"
+        ),
+    );
+    overlay.set_inner_html(&block_message());
+    let _ = body.append_child(&overlay);
+    make_modal(document, &overlay);
+}
+
+/// (background, text) colours for whatever we put on the page, going by `Config::theme`. Inline
+/// styles only, since the page doesn't have our stylesheet (so no Tailwind `dark:` either).
+pub fn overlay_colors() -> (&'static str, &'static str) {
+    if THEME.get().is_dark() {
+        ("#0f0f0f", "white")
+    } else {
+        ("#f9f9f9", "#0f0f0f")
+    }
+}
+
+/// Turns `overlay` into a modal dialog as far as assistive tech (and the keyboard) goes: labelled by
+/// its `<h1>`, with focus moved onto it (or its first button), and the rest of the page `inert` so
+/// that Tab can't wander off to the blocked stuff behind it.
+///
+/// Call again whenever the overlay's contents get replaced, like `session` does.
+pub fn make_modal(document: &web_sys::Document, overlay: &web_sys::Element) {
+    let title_id = format!("{}-title", overlay.id());
+    if let Ok(Some(title)) = overlay.query_selector("h1") {
+        title.set_id(&title_id);
+    }
+    let _ = overlay.set_attribute("role", "dialog");
+    let _ = overlay.set_attribute("aria-modal", "true");
+    let _ = overlay.set_attribute("aria-labelledby", &title_id);
+    let _ = overlay.set_attribute("tabindex", "-1");
+    let _ = overlay.remove_attribute("inert");
+
+    if let Some(body) = document.body() {
+        let children = body.children();
+        for i in 0..children.length() {
+            let Some(child) = children.item(i) else {
+                continue;
+            };
+            if child != *overlay && !child.has_attribute("inert") {
+                let _ = child.set_attribute("inert", "");
+                let _ = child.set_attribute(INERT_ATTRIBUTE, "");
+            }
+        }
+    }
+
+    let focus_target = overlay
+        .query_selector("button")
+        .ok()
+        .flatten()
+        .unwrap_or_else(|| overlay.clone());
+    if let Ok(focus_target) = focus_target.dyn_into::<web_sys::HtmlElement>() {
+        let _ = focus_target.focus();
+    }
 }
 
-fn remove_distractions(document: &web_sys::Document) {
-    let homepage = document.get_elements_by_tag_name("ytd-rich-grid-renderer");
-    let sidebar = document.get_elements_by_tag_name("ytd-watch-next-secondary-results-renderer");
+pub fn remove_block_overlay(document: &web_sys::Document, overlay_id: &str) {
+    let Some(overlay) = document.get_element_by_id(overlay_id) else {
+        return;
+    };
+    overlay.remove();
 
-    let distractions = [homepage, sidebar];
+    if let Ok(inerted) = document.query_selector_all(&format!("[{INERT_ATTRIBUTE}]")) {
+        for i in 0..inerted.length() {
+            if let Some(element) = inerted
+                .item(i)
+                .and_then(|node| node.dyn_into::<web_sys::Element>().ok())
+            {
+                let _ = element.remove_attribute("inert");
+                let _ = element.remove_attribute(INERT_ATTRIBUTE);
+            }
+        }
+    }
+    // Another overlay still up (e.g the session one over the homepage one) takes over being modal.
+    if let Ok(Some(other)) = document.query_selector(&format!("[{OVERLAY_ATTRIBUTE}]")) {
+        make_modal(document, &other);
+    }
+}
+
+/**
+ * Stricter than `remove_distractions`: during the block window, a watch page is only usable if the
+ * video's channel is on the allowlist. Search, channel pages etc. are left alone.
+ * Like the feed filter, this polls instead of running once, because YouTube navigates between videos
+ * without reloading the page (and fills in the channel info well after `document_end`).
+ */
+fn enforce_channel_allowlist(
+    window: &web_sys::Window,
+    adapter: &'static dyn SiteAdapter,
+    allowed_channels: &[String],
+) {
+    if let Some(interval) = ALLOWLIST_INTERVAL.take() {
+        window.clear_interval_with_handle(interval);
+    }
+
+    let allowed_channels: Vec<String> = allowed_channels
+        .iter()
+        .map(|channel| feed_filter::normalize_channel(channel))
+        .collect();
+    let window_clone = window.clone();
 
-    distractions.into_iter().for_each(|distraction| {
-        distraction
-            .item(0)
-            .map(|el| el.set_inner_html("<h1>🌱\nPADHLE</h1>"));
+    let closure = Closure::<dyn Fn()>::new(move || {
+        let window = &window_clone;
+        let document = window.document().expect("should have a document on window");
+
+        let Some(media) = adapter.current_media(&document) else {
+            // Not a watch page.
+            remove_block_overlay(&document, CHANNEL_OVERLAY_ID);
+            return;
+        };
+        let name = media
+            .channel_name
+            .as_deref()
+            .map(feed_filter::normalize_channel);
+        let handle = media
+            .channel_handle
+            .as_deref()
+            .map(feed_filter::normalize_channel);
+        // Channel info hasn't loaded yet, try again next tick.
+        if name.is_none() && handle.is_none() {
+            return;
+        }
+        let is_allowed = allowed_channels
+            .iter()
+            .any(|channel| Some(channel) == name.as_ref() || Some(channel) == handle.as_ref());
+
+        if is_allowed {
+            remove_block_overlay(&document, CHANNEL_OVERLAY_ID);
+        } else {
+            show_block_overlay(&document, CHANNEL_OVERLAY_ID);
+            pause_videos(&document, adapter);
+            count_block(BlockTarget::Channel);
+        }
     });
+
+    let interval = window
+        .set_interval_with_callback_and_timeout_and_arguments_0(
+            closure.as_ref().unchecked_ref(),
+            1000,
+        )
+        .expect("Failed to setInterval the channel allowlist.");
+    ALLOWLIST_INTERVAL.set(Some(interval));
+
+    // Same reasoning as in `record_watch_time`. Leaks once per block/unblock cycle, which is fine,
+    // there's only a few of those a day.
+    closure.forget();
+}
+
+pub fn pause_videos(document: &web_sys::Document, adapter: &dyn SiteAdapter) {
+    let Ok(videos) = document.query_selector_all(adapter.playback_selector()) else {
+        return;
+    };
+    for i in 0..videos.length() {
+        if let Some(video) = videos
+            .item(i)
+            .and_then(|el| el.dyn_into::<web_sys::HtmlMediaElement>().ok())
+        {
+            let _ = video.pause();
+        }
+    }
 }
@@ -0,0 +1,450 @@
+//! Sites the user added themselves (`Config::custom_sites`). There's no `SiteAdapter` for these, just
+//! a match pattern and some CSS selectors, so they get handled separately from the built-in sites.
+//!
+//! The content script only gets injected into them once the user granted us the host permission
+//! (asked for when adding the site), which is why the background (re-)registers it at runtime
+//! instead of it being in manifest.json.
+
+use crate::config::{self, BlockTarget, Config, CustomSite, Usage};
+use crate::schedule::{self, BlockState};
+use crate::{console_log, content_script};
+use std::cell::Cell;
+use wasm_bindgen::prelude::*;
+use wasm_bindgen_futures::spawn_local;
+
+/// Splits a match pattern (https://developer.mozilla.org/en-US/docs/Mozilla/Add-ons/WebExtensions/Match_patterns)
+/// into its scheme, host and path. None if it isn't one.
+fn split_pattern(pattern: &str) -> Option<(&str, &str, &str)> {
+    let (scheme, rest) = pattern.split_once("://")?;
+    let path_start = rest.find('/')?;
+    let (host, path) = rest.split_at(path_start);
+
+    let scheme_ok = matches!(scheme, "*" | "http" | "https");
+    let host_ok =
+        host == "*" || (!host.is_empty() && !host.strip_prefix("*.").unwrap_or(host).contains('*'));
+    (scheme_ok && host_ok).then_some((scheme, host, path))
+}
+
+pub fn is_valid_pattern(pattern: &str) -> bool {
+    split_pattern(pattern).is_some()
+}
+
+/// Whether a page at `scheme`(without the colon)/`host`/`path` (including the query) is covered by
+/// `pattern`. Only the http(s) subset of match patterns, that's all that matters for websites.
+pub fn matches_pattern(pattern: &str, scheme: &str, host: &str, path: &str) -> bool {
+    let Some((pattern_scheme, pattern_host, pattern_path)) = split_pattern(pattern) else {
+        return false;
+    };
+
+    let scheme_matches = match pattern_scheme {
+        "*" => scheme == "http" || scheme == "https",
+        _ => pattern_scheme == scheme,
+    };
+    let host_matches = match pattern_host.strip_prefix("*.") {
+        _ if pattern_host == "*" => true,
+        Some(domain) => host == domain || host.ends_with(&format!(".{domain}")),
+        None => pattern_host == host,
+    };
+
+    scheme_matches && host_matches && glob_matches(pattern_path, path)
+}
+
+// `*` matches any run of characters, everything else matches itself.
+fn glob_matches(glob: &str, text: &str) -> bool {
+    let mut parts = glob.split('*');
+    let first = parts.next().unwrap_or_default();
+    let Some(mut rest) = text.strip_prefix(first) else {
+        return false;
+    };
+    let mut parts: Vec<&str> = parts.collect();
+    let Some(last) = parts.pop() else {
+        // No `*` at all.
+        return rest.is_empty();
+    };
+    for part in parts {
+        match rest.find(part) {
+            Some(i) => rest = &rest[i + part.len()..],
+            None => return false,
+        }
+    }
+    rest.ends_with(last)
+}
+
+fn matches_location(site: &CustomSite, location: &web_sys::Location) -> bool {
+    let scheme = location.protocol().unwrap_or_default();
+    let host = location.hostname().unwrap_or_default();
+    let path = location.pathname().unwrap_or_default() + &location.search().unwrap_or_default();
+    matches_pattern(
+        &site.url_pattern,
+        scheme.trim_end_matches(':'),
+        &host,
+        &path,
+    )
+}
+
+fn block_state(usage: &Usage, site: &CustomSite) -> BlockState {
+    if !site.blocking.enabled {
+        return BlockState::Allowed { budget_left: None };
+    }
+    let today = config::day_key(&js_sys::Date::new_0());
+    let minutes_used = usage
+        .daily_usage
+        .get(&today)
+        .and_then(|day| day.custom_sites.get(&site.url_pattern))
+        .copied()
+        .unwrap_or_default();
+    schedule::block_state_for(&site.blocking.schedule, minutes_used)
+}
+
+thread_local! {
+    // Whether any of the custom sites covering this page are currently blocked.
+    static IS_BLOCKED: Cell<bool> = const { Cell::new(false) };
+}
+
+/// Content script side: tracks time on, and applies the selectors to, whichever custom sites cover
+/// the current page. Unlike the built-in sites, the background doesn't tell us when the block window
+/// starts/ends, so the block state gets re-checked every minute alongside the usage.
+pub fn start(window: &web_sys::Window, config: &Config, usage: &Usage) {
+    let location = window.location();
+    let sites: Vec<CustomSite> = config
+        .custom_sites
+        .iter()
+        .filter(|site| matches_location(site, &location))
+        .cloned()
+        .collect();
+    if sites.is_empty() {
+        return;
+    }
+    console_log!("On custom sites: {sites:?}");
+
+    IS_BLOCKED.set(
+        sites
+            .iter()
+            .any(|site| block_state(usage, site).is_blocked()),
+    );
+
+    let patterns: Vec<String> = sites.iter().map(|site| site.url_pattern.clone()).collect();
+    let every_minute = Closure::<dyn Fn()>::new(move || spawn_local(tick(patterns.clone())));
+    window
+        .set_interval_with_callback_and_timeout_and_arguments_0(
+            every_minute.as_ref().unchecked_ref(),
+            60 * 1000,
+        )
+        .expect("Failed to setInterval the custom site tracker.");
+    // Same as `record_watch_time`, lives as long as the page.
+    every_minute.forget();
+
+    // These are arbitrary sites, so no telling whether they're SPAs that keep rendering new stuff in.
+    // Assume they do.
+    let window_clone = window.clone();
+    let every_second = Closure::<dyn Fn()>::new(move || {
+        let document = window_clone
+            .document()
+            .expect("should have a document on window");
+        if !IS_BLOCKED.get() {
+            unhide(&document);
+            return;
+        }
+        for site in &sites {
+            let removed = content_script::remove_distractions(
+                &document,
+                &site.selectors,
+                site.selector_action,
+            );
+            if removed > 0 {
+                content_script::count_block(BlockTarget::CustomSite);
+            }
+        }
+    });
+    window
+        .set_interval_with_callback_and_timeout_and_arguments_0(
+            every_second.as_ref().unchecked_ref(),
+            1000,
+        )
+        .expect("Failed to setInterval the custom site selectors.");
+    every_second.forget();
+}
+
+async fn tick(patterns: Vec<String>) {
+    // Re-read it, the config might've changed since page load too.
+    let Ok(storage) = config::get_storage().await else {
+        return;
+    };
+    let sites: Vec<&CustomSite> = storage
+        .user_config
+        .custom_sites
+        .iter()
+        .filter(|site| patterns.contains(&site.url_pattern))
+        .collect();
+
+    let now = js_sys::Date::new_0();
+    let today = config::day_key(&now);
+    // Still need the usage when the page isn't on screen, for the daily budgets.
+    let tracked = sites.iter().any(|site| site.track_time) && content_script::page_is_visible();
+    let update = |usage: &mut Usage| {
+        for site in sites.iter().filter(|site| site.track_time) {
+            if usage.count_minute(&site.url_pattern, now.get_time() as u64) {
+                let day = usage.daily_usage.entry(today.clone()).or_default();
+                *day.custom_sites
+                    .entry(site.url_pattern.clone())
+                    .or_default() += 1;
+            }
+        }
+    };
+    let result = if tracked {
+        config::update_usage(update).await
+    } else {
+        config::get_usage().await
+    };
+    match result {
+        Ok(usage) => IS_BLOCKED.set(
+            sites
+                .iter()
+                .any(|site| block_state(&usage, site).is_blocked()),
+        ),
+        Err(e) => {
+            console_log!("Couldn't record time on custom sites: {e}");
+        }
+    }
+}
+
+// Undoes `SelectorAction::Hide`. Replaced elements are gone for good though, until a reload.
+fn unhide(document: &web_sys::Document) {
+    let Ok(elements) =
+        document.query_selector_all(&format!("[{}]", content_script::HIDDEN_ATTRIBUTE))
+    else {
+        return;
+    };
+    for i in 0..elements.length() {
+        if let Some(el) = elements
+            .item(i)
+            .and_then(|el| el.dyn_into::<web_sys::HtmlElement>().ok())
+        {
+            let _ = el.style().remove_property("display");
+            let _ = el.remove_attribute(content_script::HIDDEN_ATTRIBUTE);
+        }
+    }
+}
+
+/// Background side: `register_content_scripts` makes sure the content script gets injected into
+/// every custom site. Sites we don't have the host permission for fail to register; call
+/// `forget_registrations` once it's granted, so that the next call tries them again.
+pub use imp::{forget_registrations, register_content_scripts};
+
+#[cfg(feature = "firefox")]
+mod imp {
+    use super::*;
+    use crate::bindings::{self, object, RegisteredContentScript};
+    use std::cell::RefCell;
+
+    thread_local! {
+        // The patterns registered last time. None = register again regardless.
+        static REGISTERED_PATTERNS: RefCell<Option<Vec<String>>> = const { RefCell::new(None) };
+        static REGISTERED_SCRIPTS: RefCell<Vec<RegisteredContentScript>> =
+            const { RefCell::new(Vec::new()) };
+    }
+
+    pub fn forget_registrations() {
+        REGISTERED_PATTERNS.set(None);
+    }
+
+    pub async fn register_content_scripts(config: &Config) {
+        let patterns = patterns(config);
+        if REGISTERED_PATTERNS.with_borrow(|registered| registered.as_ref() == Some(&patterns)) {
+            return;
+        }
+        REGISTERED_PATTERNS.set(Some(patterns.clone()));
+
+        for script in REGISTERED_SCRIPTS.take() {
+            let _ = script.unregister().await;
+        }
+
+        let mut scripts = Vec::new();
+        // One at a time, so that one site we don't have permission for doesn't take the rest down.
+        for pattern in &patterns {
+            let options = object(&[
+                ("matches", js_sys::Array::of1(&pattern.into()).into()),
+                (
+                    "js",
+                    js_sys::Array::of1(&object(&[("file", "index.js".into())])).into(),
+                ),
+                ("runAt", "document_end".into()),
+            ]);
+            match bindings::content_scripts().register(&options).await {
+                Ok(script) => scripts.push(script.unchecked_into()),
+                Err(e) => {
+                    console_log!("Couldn't register the content script for {pattern}: {e:?}");
+                }
+            }
+        }
+        REGISTERED_SCRIPTS.set(scripts);
+    }
+}
+
+#[cfg(feature = "chromium")]
+mod imp {
+    use super::*;
+    use crate::bindings::{self, object};
+    use std::cell::RefCell;
+
+    thread_local! {
+        // Unlike Firefox, registrations outlive the service worker, so this starting out empty
+        // still means a fresh register the first time around.
+        static REGISTERED_PATTERNS: RefCell<Option<Vec<String>>> = const { RefCell::new(None) };
+    }
+
+    pub fn forget_registrations() {
+        REGISTERED_PATTERNS.set(None);
+    }
+
+    pub async fn register_content_scripts(config: &Config) {
+        let patterns = patterns(config);
+        if REGISTERED_PATTERNS.with_borrow(|registered| registered.as_ref() == Some(&patterns)) {
+            return;
+        }
+        REGISTERED_PATTERNS.set(Some(patterns.clone()));
+
+        let registry = bindings::script_registry();
+        // We don't register anything else, so just clear the lot.
+        let _ = registry.unregister_content_scripts().await;
+
+        for (i, pattern) in patterns.iter().enumerate() {
+            let script = object(&[
+                ("id", format!("custom-site-{i}").into()),
+                ("matches", js_sys::Array::of1(&pattern.into()).into()),
+                ("js", js_sys::Array::of1(&"index.js".into()).into()),
+                ("runAt", "document_end".into()),
+            ]);
+            if let Err(e) = registry
+                .register_content_scripts(&js_sys::Array::of1(&script))
+                .await
+            {
+                console_log!("Couldn't register the content script for {pattern}: {e:?}");
+            }
+        }
+    }
+}
+
+fn patterns(config: &Config) -> Vec<String> {
+    config
+        .custom_sites
+        .iter()
+        .map(|site| site.url_pattern.clone())
+        .filter(|pattern| is_valid_pattern(pattern))
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn scheme_wildcard() {
+        assert!(matches_pattern(
+            "*://example.com/*",
+            "http",
+            "example.com",
+            "/"
+        ));
+        assert!(matches_pattern(
+            "*://example.com/*",
+            "https",
+            "example.com",
+            "/"
+        ));
+        assert!(!matches_pattern(
+            "*://example.com/*",
+            "ftp",
+            "example.com",
+            "/"
+        ));
+        assert!(!matches_pattern(
+            "https://example.com/*",
+            "http",
+            "example.com",
+            "/"
+        ));
+    }
+
+    #[test]
+    fn host_wildcard() {
+        let pattern = "https://*.example.com/*";
+        assert!(matches_pattern(pattern, "https", "example.com", "/"));
+        assert!(matches_pattern(pattern, "https", "www.example.com", "/"));
+        assert!(matches_pattern(pattern, "https", "a.b.example.com", "/"));
+        assert!(!matches_pattern(pattern, "https", "notexample.com", "/"));
+        assert!(!matches_pattern(pattern, "https", "example.com.evil", "/"));
+        assert!(matches_pattern("https://*/*", "https", "anything.org", "/"));
+        assert!(!matches_pattern(
+            "https://example.com/*",
+            "https",
+            "www.example.com",
+            "/"
+        ));
+    }
+
+    #[test]
+    fn paths() {
+        let pattern = "https://example.com/r/*/comments/*";
+        assert!(matches_pattern(
+            pattern,
+            "https",
+            "example.com",
+            "/r/rust/comments/123"
+        ));
+        assert!(!matches_pattern(
+            pattern,
+            "https",
+            "example.com",
+            "/r/rust/"
+        ));
+        // No `*`, so only that exact page.
+        assert!(matches_pattern(
+            "https://example.com/",
+            "https",
+            "example.com",
+            "/"
+        ));
+        assert!(!matches_pattern(
+            "https://example.com/",
+            "https",
+            "example.com",
+            "/feed"
+        ));
+        // The query is part of the path.
+        assert!(matches_pattern(
+            "https://example.com/*",
+            "https",
+            "example.com",
+            "/?q=1"
+        ));
+
+        assert!(glob_matches("*", ""));
+        assert!(glob_matches("/a*b*c", "/abc"));
+        assert!(glob_matches("/a*b*c", "/a-b-b-c"));
+        assert!(!glob_matches("/a*b*c", "/a-c-b"));
+        assert!(!glob_matches("/a*", "/b"));
+    }
+
+    #[test]
+    fn rejected_patterns() {
+        for pattern in [
+            "",
+            "example.com",
+            "https://example.com",
+            "file:///home/*",
+            "ftp://example.com/*",
+            "https:///*",
+            "https://www.*.com/*",
+            "https://*example.com/*",
+        ] {
+            assert!(!is_valid_pattern(pattern), "{pattern}");
+            assert!(
+                !matches_pattern(pattern, "https", "example.com", "/"),
+                "{pattern}"
+            );
+        }
+        assert!(is_valid_pattern("*://*/*"));
+        assert!(is_valid_pattern("https://*.example.com/feed"));
+    }
+}
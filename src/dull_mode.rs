@@ -0,0 +1,120 @@
+//! Dull mode: instead of taking things off the page, make what's there less appealing. Grayscale,
+//! blurred thumbnails, no view counts, no SHOUTING titles.
+//!
+//! It's all one stylesheet, so (unlike `remove_distractions`) there's no need to poll for YouTube
+//! rendering new stuff in; CSS applies to it on its own.
+
+use crate::config::{DullMode, ThumbnailStyle};
+use crate::schedule::{self, WeekTime};
+use crate::selector_pack::{self, SelectorPack};
+use wasm_bindgen::prelude::*;
+
+const STYLE_ID: &str = "touch-grass-dull-mode";
+
+/// Turns dull mode on and off with its windows, checking every minute.
+pub fn start(window: &web_sys::Window, settings: &DullMode) {
+    if !settings.enabled || settings.windows.is_empty() {
+        return;
+    }
+
+    let css = selector_pack::with_active(|pack| stylesheet(pack, settings));
+    let windows = settings.windows.clone();
+    let document = window.document().expect("should have a document on window");
+
+    let update = move || {
+        let now = WeekTime::now();
+        let active = windows
+            .iter()
+            .any(|window| schedule::within_block_window(window, now));
+        set_style(&document, active.then_some(css.as_str()));
+    };
+    update();
+
+    let closure = Closure::<dyn Fn()>::new(update);
+    window
+        .set_interval_with_callback_and_timeout_and_arguments_0(
+            closure.as_ref().unchecked_ref(),
+            60 * 1000,
+        )
+        .expect("Failed to setInterval dull mode.");
+    // Same reasoning as in `record_watch_time`: lives as long as the page does.
+    closure.forget();
+}
+
+fn stylesheet(pack: &SelectorPack, settings: &DullMode) -> String {
+    let selectors = &pack.youtube;
+    let mut css = String::new();
+
+    if settings.grayscale {
+        css += "html { filter: grayscale(1) !important; }\n";
+    }
+
+    let thumbnail_style = match settings.thumbnails {
+        ThumbnailStyle::Normal => None,
+        ThumbnailStyle::Blur => Some("filter: blur(12px)"),
+        ThumbnailStyle::Hide => Some("opacity: 0"),
+    };
+    if let (Some(style), false) = (thumbnail_style, selectors.thumbnails.is_empty()) {
+        let hovered: Vec<String> = selectors
+            .thumbnails
+            .iter()
+            .map(|selector| format!("{selector}:hover"))
+            .collect();
+        css += &format!(
+            "{} {{ {style} !important; transition: filter 0.2s, opacity 0.2s; }}\n\
+             {} {{ filter: none !important; opacity: 1 !important; }}\n",
+            selectors.thumbnails.join(", "),
+            hovered.join(", "),
+        );
+    }
+
+    if settings.hide_view_counts && !selectors.view_counts.is_empty() {
+        css += &format!(
+            "{} {{ display: none !important; }}\n",
+            selectors.view_counts.join(", ")
+        );
+    }
+
+    if settings.tame_titles && !selectors.titles.is_empty() {
+        // Lowercasing everything also gets the titles that were fine to begin with, but there's no
+        // telling them apart from CSS. Sentence case it is.
+        let first_letters: Vec<String> = selectors
+            .titles
+            .iter()
+            .map(|selector| format!("{selector}::first-letter"))
+            .collect();
+        css += &format!(
+            "{} {{ text-transform: lowercase !important; font-weight: normal !important; }}\n\
+             {} {{ text-transform: uppercase !important; }}\n",
+            selectors.titles.join(", "),
+            first_letters.join(", "),
+        );
+    }
+
+    css
+}
+
+/// None takes the stylesheet out again.
+fn set_style(document: &web_sys::Document, css: Option<&str>) {
+    let existing = document.get_element_by_id(STYLE_ID);
+    match (css, existing) {
+        (Some(css), Some(style)) => {
+            if style.text_content().as_deref() != Some(css) {
+                style.set_text_content(Some(css));
+            }
+        }
+        (Some(css), None) => {
+            let Some(head) = document.head() else {
+                return;
+            };
+            let style = document
+                .create_element("style")
+                .expect("Creating a style should never fail");
+            style.set_id(STYLE_ID);
+            style.set_text_content(Some(css));
+            let _ = head.append_child(&style);
+        }
+        (None, Some(style)) => style.remove(),
+        (None, None) => {}
+    }
+}
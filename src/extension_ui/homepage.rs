@@ -1,6 +1,24 @@
-use crate::{config, console_log};
+use super::theme;
+use super::weekday_toggle::WeekdayToggle;
+use crate::{
+    bindings::{self, browser},
+    config::{
+        self, BlockAction, ConfigProblem, CustomSite, DayUsage, DullMode, FilterRules, Language,
+        RedirectTarget, ReportDelivery, Schedule, SelectorAction, ShortsPageAction, Site,
+        SiteConfig, Theme, ThumbnailStyle,
+    },
+    console_log, custom_sites, feed_filter,
+    i18n::{self, fill, t},
+    messages::{Message, SelfTestReport},
+    report,
+    selector_pack::{self, PackError, SelectorPack},
+    stats::{self, Period},
+    streaks::{self, Achievement, DayRating, StreakKind},
+};
 use dioxus::prelude::*;
+use std::collections::BTreeMap;
 use wasm_bindgen::prelude::*;
+use web_extensions_sys::Tab;
 
 #[wasm_bindgen]
 pub fn start_app() {
@@ -13,31 +31,40 @@ fn app() -> Element {
     // Depending on that, we either render the welcome screen or the normal setting screen.
     use config::StorageError;
     let page = use_resource(move || async move {
-        match config::get_storage().await {
-            Ok(config) => show_settings(Some(config)),
+        let storage = config::get_storage().await;
+        let config = storage
+            .as_ref()
+            .map(|storage| storage.user_config.clone())
+            .unwrap_or_default();
+        theme::apply(config.theme);
+        i18n::set_language(config.language);
+        i18n::apply_to_page();
+        match storage {
+            Ok(config) => {
+                // The stats are a nice to have, not worth an error page of their own.
+                let usage = config::get_usage().await.unwrap_or_else(|e| {
+                    console_log!("Couldn't get the usage: {e}");
+                    Default::default()
+                });
+                show_settings(Some(config), usage)
+            }
             Err(StorageError::EmptyStorage) => show_welcome_screen(),
             Err(StorageError::StorageNotFound) => rsx!(
-                h3 {
-                    "Storage bucket not found! :( It either seems like you are either using a very old browser
-                    or you're not running it in one"
-                }
+                h3 { {t().storage_not_found} }
             ),
             Err(StorageError::WontAllowStorage) => rsx!(
-                h3 {
-                    "You need to allow storage for this extension to work!"
-                }
+                h3 { {t().storage_not_allowed} }
             ),
             Err(StorageError::CorruptedConfig) => {
                 // We need to remove the config and then show the welcome screen
-                config::remove_storage()
-                    .await
-                    .expect("Couldn't set the default config");
-
-                rsx!(
-                    h3 {
-                        "The config is corrupted! We're gonna have to start from scratch! Try re-opening the extension popup"
-                    }
-                )
+                match config::remove_storage().await {
+                    Ok(()) => rsx!(
+                        h3 { {t().config_corrupted} }
+                    ),
+                    Err(e) => rsx!(
+                        h3 { {fill(t().config_corrupted_unclearable, &[("error", &e)])} }
+                    ),
+                }
             }
         }
     });
@@ -48,7 +75,7 @@ fn app() -> Element {
             {page}
         }
     } else {
-        rsx! { "Loading..." }
+        rsx! { {t().loading} }
     }
 }
 
@@ -60,26 +87,1045 @@ fn parse_time(time: &str) -> Option<u32> {
     })
 }
 
+// Values of the <option>s. What the user sees is `block_action_label()`.
+const BLOCK_ACTION_KINDS: [&str; 6] = [
+    "blank",
+    "subscriptions",
+    "watch-later",
+    "playlist",
+    "url",
+    "overlay",
+];
+
+fn block_action_label(kind: &str) -> &'static str {
+    match kind {
+        "subscriptions" => t().action_subscriptions,
+        "watch-later" => t().action_watch_later,
+        "playlist" => t().action_playlist,
+        "url" => t().action_url,
+        "overlay" => t().action_overlay,
+        _ => t().action_blank,
+    }
+}
+
+fn block_action_kind(action: &BlockAction) -> &'static str {
+    match action {
+        BlockAction::Blank => "blank",
+        BlockAction::Redirect(RedirectTarget::Subscriptions) => "subscriptions",
+        BlockAction::Redirect(RedirectTarget::WatchLater) => "watch-later",
+        BlockAction::Redirect(RedirectTarget::Playlist(_)) => "playlist",
+        BlockAction::Redirect(RedirectTarget::Url(_)) => "url",
+        BlockAction::Overlay => "overlay",
+    }
+}
+
+fn block_action_from_kind(kind: &str) -> BlockAction {
+    match kind {
+        "subscriptions" => BlockAction::Redirect(RedirectTarget::Subscriptions),
+        "watch-later" => BlockAction::Redirect(RedirectTarget::WatchLater),
+        "playlist" => BlockAction::Redirect(RedirectTarget::Playlist(String::new())),
+        "url" => BlockAction::Redirect(RedirectTarget::Url(String::new())),
+        "overlay" => BlockAction::Overlay,
+        _ => BlockAction::Blank,
+    }
+}
+
+// One rule per line:
+// - `@handle` or `channel:Channel Name` matches a channel
+// - `/pattern/` is a (case-insensitive) regex on the title
+// - anything else is a keyword that the title should contain
+fn parse_filter_rules(text: &str) -> FilterRules {
+    let mut rules = FilterRules::default();
+    for line in text.lines().map(str::trim).filter(|line| !line.is_empty()) {
+        if let Some(channel) = line.strip_prefix("channel:") {
+            rules.channels.push(channel.trim().to_owned());
+        } else if line.starts_with('@') {
+            rules.channels.push(line.to_owned());
+        } else if let Some(regex) = line
+            .strip_prefix('/')
+            .and_then(|line| line.strip_suffix('/'))
+            .filter(|regex| !regex.is_empty())
+        {
+            rules.regexes.push(regex.to_owned());
+        } else {
+            rules.keywords.push(line.to_owned());
+        }
+    }
+    rules
+}
+
+fn format_filter_rules(rules: &FilterRules) -> String {
+    let channels = rules.channels.iter().map(|channel| {
+        if channel.starts_with('@') {
+            channel.clone()
+        } else {
+            format!("channel:{channel}")
+        }
+    });
+    let regexes = rules.regexes.iter().map(|regex| format!("/{regex}/"));
+    let keywords = rules.keywords.iter().cloned();
+
+    channels
+        .chain(regexes)
+        .chain(keywords)
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
+#[component]
+fn FilterRulesInput(
+    id: &'static str,
+    label: &'static str,
+    rules: FilterRules,
+    on_change: EventHandler<FilterRules>,
+) -> Element {
+    // The textarea keeps its own raw text. Re-deriving it from `rules` on every keystroke would eat
+    // half-typed lines (like a trailing newline).
+    let mut text = use_signal(|| format_filter_rules(&rules));
+    let has_invalid_regex = rules
+        .regexes
+        .iter()
+        .any(|regex| !feed_filter::is_valid_regex(regex));
+
+    rsx!(
+        div {
+            label {
+                class: "text-sm font-medium leading-none peer-disabled:cursor-not-allowed peer-disabled:opacity-70",
+                r#for: "{id}",
+                "{label}"
+            }
+            textarea {
+                class: "flex w-full rounded-md border border-input bg-background mt-2 px-3 py-2 text-sm ring-offset-background placeholder:text-muted-foreground focus-visible:outline-none focus-visible:ring-2 focus-visible:ring-ring focus-visible:ring-offset-2",
+                class: if has_invalid_regex { "red-border" },
+                id: "{id}",
+                rows: 3,
+                placeholder: t().filter_rules_placeholder,
+                aria_invalid: "{has_invalid_regex}",
+                aria_describedby: "{id}-error",
+                value: "{text}",
+                oninput: move |evt| {
+                    let rules = parse_filter_rules(&evt.value());
+                    text.set(evt.value());
+                    on_change.call(rules);
+                },
+            }
+            if has_invalid_regex {
+                p { id: "{id}-error", class: "text-xs text-red-600 mt-1", {t().invalid_regex} }
+            }
+        }
+    )
+}
+
+/// A textarea where every non-empty line is one entry.
+#[component]
+fn LinesInput(
+    id: String,
+    label: &'static str,
+    placeholder: &'static str,
+    lines: Vec<String>,
+    on_change: EventHandler<Vec<String>>,
+) -> Element {
+    // Same deal as FilterRulesInput, keep the raw text around.
+    let mut text = use_signal(|| lines.join("\n"));
+
+    rsx!(
+        div {
+            label {
+                class: "text-sm font-medium leading-none peer-disabled:cursor-not-allowed peer-disabled:opacity-70",
+                r#for: "{id}",
+                "{label}"
+            }
+            textarea {
+                class: "flex w-full rounded-md border border-input bg-background mt-2 px-3 py-2 text-sm ring-offset-background placeholder:text-muted-foreground focus-visible:outline-none focus-visible:ring-2 focus-visible:ring-ring focus-visible:ring-offset-2",
+                id: "{id}",
+                rows: 3,
+                placeholder: "{placeholder}",
+                value: "{text}",
+                oninput: move |evt| {
+                    let lines = evt
+                        .value()
+                        .lines()
+                        .map(str::trim)
+                        .filter(|line| !line.is_empty())
+                        .map(str::to_owned)
+                        .collect();
+                    text.set(evt.value());
+                    on_change.call(lines);
+                },
+            }
+        }
+    )
+}
+
+/// Enable/disable blocking for a non-YouTube site, plus its own block window, days and budget.
+#[component]
+fn SiteScheduleEditor(
+    // Prefix for the element IDs.
+    id: String,
+    name: String,
+    // ReadOnlySignal rather than the plain value so that `edit` below can be Copy.
+    site_config: ReadOnlySignal<SiteConfig>,
+    on_change: EventHandler<SiteConfig>,
+    // Next to the enable checkbox. `Catalog::block_site` if not given.
+    enable_label: Option<String>,
+) -> Element {
+    let enabled = site_config.read().enabled;
+
+    // Every input edits one part of the config, and hands the whole thing back up.
+    let edit = move |f: &dyn Fn(&mut SiteConfig)| {
+        let mut site_config = site_config();
+        f(&mut site_config);
+        on_change.call(site_config);
+    };
+
+    rsx!(
+        fieldset { class: "grid gap-2",
+            div { class: "flex items-center gap-2",
+                input {
+                    id: "{id}-enabled",
+                    r#type: "checkbox",
+                    checked: enabled,
+                    onchange: move |evt| edit(&|c| c.enabled = evt.checked()),
+                }
+                label { class: "text-sm font-medium", r#for: "{id}-enabled",
+                    {enable_label.unwrap_or_else(|| fill(t().block_site, &[("name", &name)]))}
+                }
+            }
+            if enabled {
+                ScheduleInputs {
+                    id: id.clone(),
+                    name: name.clone(),
+                    schedule: site_config.read().schedule.clone(),
+                    on_change: move |schedule: Schedule| edit(&|c| c.schedule = schedule.clone()),
+                }
+            }
+        }
+    )
+}
+
+/// The window, days and (optionally) budget of one `Schedule`.
+#[component]
+fn ScheduleInputs(
+    // Prefix for the element IDs.
+    id: String,
+    // What the schedule is for, for the screen reader labels.
+    name: String,
+    schedule: ReadOnlySignal<Schedule>,
+    on_change: EventHandler<Schedule>,
+    #[props(default = true)] with_budget: bool,
+) -> Element {
+    let current = schedule();
+    let format_time = |time: u32| format!("{:02}:{:02}", time / 60, time % 60);
+    // Same checks as in `Config::problems()`, for next to the inputs they're about.
+    let problems = if with_budget {
+        current.problems(&name)
+    } else {
+        current.window_problems(&name)
+    };
+    let empty_window = problems
+        .iter()
+        .any(|problem| matches!(problem, ConfigProblem::EmptyWindow { .. }));
+    let bad_budget = problems
+        .iter()
+        .any(|problem| matches!(problem, ConfigProblem::BudgetOutOfRange { .. }));
+
+    let edit = move |f: &dyn Fn(&mut Schedule)| {
+        let mut schedule = schedule();
+        f(&mut schedule);
+        on_change.call(schedule);
+    };
+
+    rsx!(
+        div { class: "flex flex-row gap-2 items-center",
+            input {
+                class: "rounded-md border border-input bg-background px-2 py-1 text-sm",
+                class: if empty_window { "red-border" },
+                aria_label: fill(t().site_start_time, &[("name", &name)]),
+                aria_invalid: "{empty_window}",
+                aria_describedby: "{id}-error",
+                r#type: "time",
+                value: format_time(current.block_time_start),
+                oninput: move |evt| {
+                    if let Some(time) = parse_time(&evt.value()) {
+                        edit(&|c| c.block_time_start = time);
+                    }
+                },
+            }
+            span { class: "text-sm", {t().time_range_to} }
+            input {
+                class: "rounded-md border border-input bg-background px-2 py-1 text-sm",
+                class: if empty_window { "red-border" },
+                aria_label: fill(t().site_end_time, &[("name", &name)]),
+                aria_invalid: "{empty_window}",
+                aria_describedby: "{id}-error",
+                r#type: "time",
+                value: format_time(current.block_time_end),
+                oninput: move |evt| {
+                    if let Some(time) = parse_time(&evt.value()) {
+                        edit(&|c| c.block_time_end = time);
+                    }
+                },
+            }
+        }
+        WeekdayToggle {
+            id: "{id}-days",
+            active_days: current.active_days,
+            label: fill(t().site_block_days, &[("name", &name)]),
+            on_change: move |active_days| edit(&|c| c.active_days = active_days),
+        }
+        if with_budget {
+            input {
+                class: "rounded-md border border-input bg-background px-2 py-1 text-sm",
+                class: if bad_budget { "red-border" },
+                aria_label: fill(t().site_budget_label, &[("name", &name)]),
+                aria_invalid: "{bad_budget}",
+                aria_describedby: "{id}-error",
+                r#type: "number",
+                min: "1",
+                max: "{config::MAX_DAILY_BUDGET}",
+                placeholder: t().site_budget_placeholder,
+                value: current.daily_budget.map(|budget| budget.to_string()).unwrap_or_default(),
+                oninput: move |evt| {
+                    let budget = evt.value().parse::<u32>().ok();
+                    edit(&|c| c.daily_budget = budget);
+                },
+            }
+        }
+        if !problems.is_empty() {
+            div { id: "{id}-error", class: "text-xs text-red-600",
+                for problem in problems.iter() {
+                    p { "{problem}" }
+                }
+            }
+        }
+    )
+}
+
+/// Dull mode can have any number of windows, unlike blocking.
+#[component]
+fn DullModeWindows(config_signal: Signal<Option<config::Config>>) -> Element {
+    let (enabled, windows) = config_signal
+        .read()
+        .as_ref()
+        .map(|c| (c.dull_mode.enabled, c.dull_mode.windows.clone()))
+        .unwrap_or_default();
+
+    rsx!(
+        div { class: "flex items-center gap-2",
+            input {
+                id: "dull-mode-enabled",
+                r#type: "checkbox",
+                checked: enabled,
+                onchange: move |evt| {
+                    let mut config = config_signal.write();
+                    let dull_mode = &mut config.get_or_insert_default().dull_mode;
+                    dull_mode.enabled = evt.checked();
+                    // Something to edit straight away.
+                    if dull_mode.enabled && dull_mode.windows.is_empty() {
+                        dull_mode.windows.push(Schedule::default());
+                    }
+                },
+            }
+            label { class: "text-sm font-medium", r#for: "dull-mode-enabled", {t().dull_mode_enable} }
+        }
+        if enabled {
+            for (i, window) in windows.into_iter().enumerate() {
+                div { key: "{i}", class: "grid gap-2",
+                    div { class: "flex items-center justify-between",
+                        span { class: "text-sm", {DullMode::window_name(i)} }
+                        button {
+                            class: "text-xs text-red-600 hover:underline",
+                            aria_label: fill(t().remove_site, &[("name", &DullMode::window_name(i))]),
+                            onclick: move |_| {
+                                config_signal.write().get_or_insert_default().dull_mode.windows.remove(i);
+                                focus_element(DULL_MODE_ADD_WINDOW_ID);
+                            },
+                            {t().remove}
+                        }
+                    }
+                    ScheduleInputs {
+                        id: "dull-mode-{i}",
+                        name: DullMode::window_name(i),
+                        schedule: window,
+                        with_budget: false,
+                        on_change: move |window| {
+                            if let Some(slot) = config_signal.write().get_or_insert_default().dull_mode.windows.get_mut(i) {
+                                *slot = window;
+                            }
+                        },
+                    }
+                }
+            }
+            button {
+                id: DULL_MODE_ADD_WINDOW_ID,
+                class: "text-sm underline justify-self-start",
+                onclick: move |_| config_signal.write().get_or_insert_default().dull_mode.windows.push(Schedule::default()),
+                {t().dull_mode_add_window}
+            }
+        }
+    )
+}
+
+const DULL_MODE_ADD_WINDOW_ID: &str = "dull-mode-add-window";
+
+const NEW_SITE_INPUT_ID: &str = "custom-site-pattern";
+
+/// For keeping the keyboard focus somewhere sensible when what had it goes away.
+fn focus_element(id: &str) {
+    let element = web_sys::window()
+        .and_then(|window| window.document())
+        .and_then(|document| document.get_element_by_id(id))
+        .and_then(|element| element.dyn_into::<web_sys::HtmlElement>().ok());
+    if let Some(element) = element {
+        let _ = element.focus();
+    }
+}
+
+/// Sites the user adds themselves, see `custom_sites`.
+#[component]
+fn CustomSitesEditor(config_signal: Signal<Option<config::Config>>) -> Element {
+    let mut new_pattern = use_signal(String::new);
+    // Shown under the add form. Doubles as the "permission denied" message.
+    let mut add_error: Signal<Option<String>> = use_signal(|| None);
+    let custom_sites = config_signal
+        .read()
+        .as_ref()
+        .map(|c| c.custom_sites.clone())
+        .unwrap_or_default();
+
+    let add_site = move |_| {
+        let pattern = new_pattern().trim().to_owned();
+        if !custom_sites::is_valid_pattern(&pattern) {
+            add_error.set(Some(t().invalid_pattern.to_owned()));
+            return;
+        }
+        let already_added = config_signal.read().as_ref().is_some_and(|c| {
+            c.custom_sites
+                .iter()
+                .any(|site| site.url_pattern == pattern)
+        });
+        if already_added {
+            add_error.set(Some(t().site_already_added.to_owned()));
+            return;
+        }
+
+        // Asking has to happen right here in the click handler, not after an await.
+        let granted = bindings::permissions().request(&bindings::object(&[(
+            "origins",
+            js_sys::Array::of1(&pattern.as_str().into()).into(),
+        )]));
+
+        let site = CustomSite {
+            url_pattern: pattern,
+            selectors: Vec::new(),
+            selector_action: SelectorAction::Hide,
+            track_time: true,
+            blocking: SiteConfig::default(),
+        };
+        config_signal
+            .write()
+            .get_or_insert_default()
+            .custom_sites
+            .push(site.clone());
+        new_pattern.set(String::new());
+        add_error.set(None);
+        // Saved right away instead of waiting on the Save button, as the permission prompt can close
+        // the popup (it does on Firefox). Only the new site though: whatever else is being edited
+        // hasn't been checked yet, and is for the Save button to save.
+        spawn(async move {
+            let result = config::update_storage(|storage| {
+                let sites = &mut storage.user_config.custom_sites;
+                if !sites.iter().any(|s| s.url_pattern == site.url_pattern) {
+                    sites.push(site);
+                }
+            })
+            .await;
+            if let Err(e) = result {
+                add_error.set(Some(fill(t().custom_site_save_failed, &[("error", &e)])));
+                return;
+            }
+            match wasm_bindgen_futures::JsFuture::from(granted).await {
+                Ok(granted) if granted.as_bool() == Some(true) => {}
+                _ => add_error.set(Some(t().no_site_permission.to_owned())),
+            }
+        });
+    };
+
+    rsx!(
+        div { class: "grid gap-4 mt-2",
+            for (i, site) in custom_sites.into_iter().enumerate() {
+                fieldset {
+                    key: "{site.url_pattern}",
+                    class: "grid gap-2 rounded-md border p-2",
+                    aria_label: "{site.url_pattern}",
+                    div { class: "flex items-center justify-between gap-2",
+                        span { class: "text-sm font-medium break-all", "{site.url_pattern}" }
+                        button {
+                            class: "text-xs text-red-600 hover:underline",
+                            aria_label: fill(t().remove_site, &[("name", &site.url_pattern)]),
+                            onclick: move |_| {
+                                config_signal.write().get_or_insert_default().custom_sites.remove(i);
+                                // The button's gone with the site, which would drop the focus back
+                                // to the top of the popup.
+                                focus_element(NEW_SITE_INPUT_ID);
+                            },
+                            {t().remove}
+                        }
+                    }
+                    LinesInput {
+                        id: "custom-site-{i}-selectors",
+                        label: t().css_selectors,
+                        placeholder: t().css_selectors_placeholder,
+                        lines: site.selectors.clone(),
+                        on_change: move |selectors| {
+                            config_signal.write().get_or_insert_default().custom_sites[i].selectors = selectors;
+                        },
+                    }
+                    select {
+                        class: "rounded-md border border-input bg-background px-2 py-1 text-sm",
+                        aria_label: t().selector_action,
+                        onchange: move |evt| {
+                            let action = if evt.value() == "replace" { SelectorAction::Replace } else { SelectorAction::Hide };
+                            config_signal.write().get_or_insert_default().custom_sites[i].selector_action = action;
+                        },
+                        option { value: "hide", selected: site.selector_action == SelectorAction::Hide, {t().selector_hide} }
+                        option { value: "replace", selected: site.selector_action == SelectorAction::Replace, {t().selector_replace} }
+                    }
+                    div { class: "flex items-center gap-2",
+                        input {
+                            id: "custom-site-{i}-track-time",
+                            r#type: "checkbox",
+                            checked: site.track_time,
+                            onchange: move |evt| {
+                                config_signal.write().get_or_insert_default().custom_sites[i].track_time = evt.checked();
+                            },
+                        }
+                        label { class: "text-sm", r#for: "custom-site-{i}-track-time", {t().track_time} }
+                    }
+                    SiteScheduleEditor {
+                        id: "custom-site-{i}",
+                        name: site.url_pattern.clone(),
+                        site_config: site.blocking.clone(),
+                        on_change: move |blocking| {
+                            config_signal.write().get_or_insert_default().custom_sites[i].blocking = blocking;
+                        },
+                    }
+                }
+            }
+            div { class: "flex flex-row gap-2",
+                input {
+                    class: "flex h-10 w-full rounded-md border border-input bg-background px-3 py-2 text-sm ring-offset-background placeholder:text-muted-foreground focus-visible:outline-none focus-visible:ring-2 focus-visible:ring-ring focus-visible:ring-offset-2",
+                    class: if add_error.read().is_some() { "red-border" },
+                    id: NEW_SITE_INPUT_ID,
+                    aria_label: t().pattern_to_add,
+                    aria_invalid: "{add_error.read().is_some()}",
+                    aria_describedby: "custom-site-error",
+                    placeholder: "*://*.example.com/*",
+                    value: "{new_pattern}",
+                    oninput: move |evt| new_pattern.set(evt.value()),
+                }
+                button {
+                    class: "inline-flex items-center justify-center rounded-md text-sm font-medium border border-input h-10 px-4",
+                    onclick: add_site,
+                    {t().add}
+                }
+            }
+            if let Some(error) = add_error() {
+                p { id: "custom-site-error", class: "text-xs text-red-600", role: "alert", "{error}" }
+            }
+        }
+    )
+}
+
+/// Loading newer selector packs, and checking the current one against a YouTube tab.
+#[component]
+fn SelectorPackSettings(
+    config_signal: Signal<Option<config::Config>>,
+    stored_pack: Option<SelectorPack>,
+) -> Element {
+    let bundled_version = SelectorPack::bundled().version;
+    let mut in_use = use_signal(|| {
+        stored_pack
+            .map(|pack| pack.version)
+            .unwrap_or_default()
+            .max(bundled_version)
+    });
+    let mut status: Signal<Option<String>> = use_signal(|| None);
+    let mut self_test: Signal<Option<SelfTestReport>> = use_signal(|| None);
+
+    let mut on_installed = move |result: Result<SelectorPack, PackError>| match result {
+        Ok(pack) => {
+            in_use.set(pack.version);
+            status.set(Some(fill(
+                t().pack_now_using,
+                &[("version", &pack.version)],
+            )));
+        }
+        Err(e) => status.set(Some(e.to_string())),
+    };
+
+    let run_self_test = move |_| {
+        spawn(async move {
+            self_test.set(None);
+            let tabs = browser()
+                .tabs()
+                .query(&bindings::object(&[
+                    ("active", true.into()),
+                    ("currentWindow", true.into()),
+                ]))
+                .await
+                .map(|tabs| js_sys::Array::from(&tabs));
+            let tab_id = tabs
+                .ok()
+                .and_then(|tabs| tabs.get(0).unchecked_into::<Tab>().id());
+            let report = match tab_id {
+                Some(tab_id) => browser()
+                    .tabs()
+                    .send_message(tab_id, &Message::RunSelfTest.to_js(), None)
+                    .await
+                    .ok()
+                    .and_then(SelfTestReport::from_js),
+                None => None,
+            };
+            match report {
+                Some(report) => {
+                    status.set(None);
+                    self_test.set(Some(report));
+                }
+                None => status.set(Some(t().self_test_failed.to_owned())),
+            }
+        });
+    };
+
+    rsx!(
+        div { class: "grid gap-2 mt-2",
+            p { class: "text-sm",
+                {fill(t().pack_versions, &[("bundled", &bundled_version), ("in_use", &in_use)])}
+            }
+            label { class: "text-sm", r#for: "selector-pack-file", {t().load_pack_file} }
+            input {
+                class: "text-sm",
+                id: "selector-pack-file",
+                r#type: "file",
+                accept: ".json,application/json",
+                onchange: move |evt| {
+                    let Some(files) = evt.files() else {
+                        return;
+                    };
+                    spawn(async move {
+                        let Some(name) = files.files().into_iter().next() else {
+                            return;
+                        };
+                        match files.read_file_to_string(&name).await {
+                            Some(json) => on_installed(selector_pack::install(&json).await),
+                            None => status.set(Some(fill(t().couldnt_read_file, &[("name", &name)]))),
+                        }
+                    });
+                },
+            }
+            p { class: "text-xs text-gray-500 dark:text-gray-400",
+                {t().firefox_file_picker}
+            }
+            label { class: "text-sm", r#for: "selector-pack-url", {t().pack_url} }
+            div { class: "flex flex-row gap-2",
+                input {
+                    class: "flex h-10 w-full rounded-md border border-input bg-background px-3 py-2 text-sm ring-offset-background placeholder:text-muted-foreground focus-visible:outline-none focus-visible:ring-2 focus-visible:ring-ring focus-visible:ring-offset-2",
+                    id: "selector-pack-url",
+                    placeholder: "https://...",
+                    initial_value: config_signal.read().as_ref().and_then(|c| c.selector_pack_url.clone()).unwrap_or_default(),
+                    oninput: move |evt| {
+                        let url = Some(evt.value().trim().to_owned()).filter(|url| !url.is_empty());
+                        config_signal.write().get_or_insert_default().selector_pack_url = url;
+                    },
+                }
+                button {
+                    class: "inline-flex items-center justify-center rounded-md text-sm font-medium border border-input h-10 px-4 whitespace-nowrap",
+                    onclick: move |_| {
+                        let Some(url) = config_signal.read().as_ref().and_then(|c| c.selector_pack_url.clone()) else {
+                            status.set(Some(t().enter_url_first.to_owned()));
+                            return;
+                        };
+                        spawn(async move { on_installed(selector_pack::install_from_url(&url).await) });
+                    },
+                    {t().check_now}
+                }
+            }
+            button {
+                class: "inline-flex items-center justify-center rounded-md text-sm font-medium border border-input h-10 px-4",
+                onclick: run_self_test,
+                {t().self_test}
+            }
+            // Always there (if empty), as screen readers only announce changes to a live region that
+            // already existed.
+            div { role: "status", aria_live: "polite",
+                if let Some(status) = status() {
+                    p { class: "text-xs", "{status}" }
+                }
+            }
+            if let Some(report) = self_test() {
+                div { class: "text-xs grid gap-1", role: "status", aria_live: "polite",
+                    p { class: "break-all",
+                        {fill(t().self_test_page, &[("url", &report.url), ("version", &report.pack_version)])}
+                    }
+                    if report.tested.is_empty() {
+                        p { {t().self_test_nothing} }
+                    } else if report.unmatched.is_empty() {
+                        p { {fill(t().self_test_all_matched, &[("count", &report.tested.len())])} }
+                    } else {
+                        p { {t().self_test_unmatched} }
+                        ul {
+                            for selector in report.unmatched {
+                                li { class: "font-mono text-red-600", "{selector}" }
+                            }
+                        }
+                    }
+                }
+            }
+        }
+    )
+}
+
+#[component]
+fn Streaks(daily_usage: BTreeMap<String, DayUsage>, daily_budget: Option<u32>) -> Element {
+    // All there is, see `config::USAGE_KEPT_WEEKS`.
+    const WEEKS: u32 = config::USAGE_KEPT_WEEKS;
+    let streaks: Vec<_> = StreakKind::ALL
+        .into_iter()
+        .map(|kind| (kind, streaks::streak(&daily_usage, daily_budget, kind)))
+        .collect();
+    let calendar = streaks::calendar(&daily_usage, daily_budget, WEEKS);
+
+    rsx!(
+        div { class: "p-6 pt-0 grid gap-4",
+            h3 { class: "font-semibold tracking-tight", {t().streaks} }
+            for (kind, streak) in streaks.iter().copied() {
+                div { class: "flex justify-between gap-2 text-sm",
+                    span { {kind.label()} }
+                    if let Some(streak) = streak {
+                        span { class: "whitespace-nowrap",
+                            {fill(t().streak_days, &[("current", &streak.current), ("best", &streak.best)])}
+                        }
+                    } else {
+                        span { class: "text-xs text-gray-500 dark:text-gray-400", {t().set_budget_first} }
+                    }
+                }
+            }
+            // Columns are weeks, rows Monday to Sunday, like GitHub's.
+            div { class: "grid grid-rows-7 grid-flow-col gap-1 justify-start",
+                for (day, rating) in calendar {
+                    div {
+                        class: "w-3 h-3 rounded-sm",
+                        class: match rating {
+                            DayRating::Unused => "bg-gray-200 dark:bg-gray-700",
+                            DayRating::Good => "bg-green-500",
+                            DayRating::Bad => "bg-red-500",
+                        },
+                        title: "{day}",
+                        // The color's the whole point, so it has to be spelled out for screen readers.
+                        role: "img",
+                        aria_label: {
+                            let rating = match rating {
+                                DayRating::Unused => t().day_unused,
+                                DayRating::Good => t().day_good,
+                                DayRating::Bad => t().day_bad,
+                            };
+                            format!("{day}: {rating}")
+                        },
+                    }
+                }
+            }
+            div {
+                h4 { class: "text-sm font-medium", {t().achievements} }
+                ul { class: "text-sm",
+                    for (achievement, (name, description)) in Achievement::ALL.map(|achievement| (achievement, achievement.text())) {
+                        li {
+                            class: if !achievement.is_earned(&streaks) { "text-gray-400" },
+                            title: "{description}",
+                            if achievement.is_earned(&streaks) { "🏆 " } else { "🔒 " }
+                            "{name}: {description}"
+                        }
+                    }
+                }
+            }
+        }
+    )
+}
+
+#[component]
+fn UsageStats(daily_usage: BTreeMap<String, DayUsage>) -> Element {
+    const TOP_N: usize = 5;
+    let mut period = use_signal(|| Period::Today);
+
+    let total = stats::total_minutes(&daily_usage, period());
+    let top_channels = stats::top_channels(&daily_usage, period(), TOP_N);
+    let top_videos = stats::top_videos(&daily_usage, period(), TOP_N);
+    let top_other_sites = stats::top_other_sites(&daily_usage, period(), TOP_N);
+    let blocked = stats::blocked_counts(&daily_usage, period());
+
+    let ranking = |heading: &'static str, items: Vec<(String, u32)>| {
+        rsx! {
+            div {
+                h4 { class: "text-sm font-medium", "{heading}" }
+                if items.is_empty() {
+                    p { class: "text-xs text-gray-500 dark:text-gray-400", {t().nothing_yet} }
+                }
+                ol { class: "text-sm",
+                    for (name, minutes) in items {
+                        li { class: "flex justify-between gap-2",
+                            span { class: "truncate", title: "{name}", "{name}" }
+                            span { class: "whitespace-nowrap", {stats::format_minutes(minutes)} }
+                        }
+                    }
+                }
+            }
+        }
+    };
+
+    rsx!(
+        div { class: "p-6 pt-0 grid gap-4",
+            div { class: "flex flex-row justify-between items-center",
+                h3 { class: "font-semibold tracking-tight", {t().watch_time} }
+                div { class: "flex gap-1",
+                    for p in Period::ALL {
+                        button {
+                            class: "rounded-md px-2 py-1 text-xs",
+                            class: if p == period() { "bg-primary text-white" } else { "border border-input" },
+                            aria_pressed: "{p == period()}",
+                            onclick: move |_| period.set(p),
+                            {p.label()}
+                        }
+                    }
+                }
+            }
+            p { class: "text-sm", {t().total} {stats::format_minutes(total)} }
+            {ranking(t().top_channels, top_channels)}
+            {ranking(t().top_videos, top_videos)}
+            {ranking(t().other_sites, top_other_sites)}
+            div {
+                h4 { class: "text-sm font-medium",
+                    {fill(t().temptations_resisted, &[("count", &blocked.iter().map(|(_, count)| count).sum::<u32>())])}
+                }
+                ul { class: "text-sm",
+                    for (target, count) in blocked {
+                        li { class: "flex justify-between gap-2",
+                            span { {target.label()} }
+                            span { "{count}" }
+                        }
+                    }
+                }
+            }
+            HourlyHeatmap { daily_usage }
+        }
+    )
+}
+
+// Weeks that the heatmap and the block window suggestions go by.
+const HEATMAP_WEEKS: u32 = 4;
+
+#[component]
+fn HourlyHeatmap(daily_usage: BTreeMap<String, DayUsage>) -> Element {
+    let heatmap = stats::hourly_heatmap(&daily_usage, HEATMAP_WEEKS);
+    let max = heatmap.iter().flatten().copied().max().unwrap_or(0).max(1);
+
+    rsx!(
+        div {
+            h4 { class: "text-sm font-medium", {fill(t().by_hour, &[("weeks", &HEATMAP_WEEKS)])} }
+            // A column for the day initials, then one per hour.
+            div { class: "grid grid-cols-[1rem_repeat(24,minmax(0,1fr))] gap-px text-xs",
+                span {}
+                // The cells' labels already say the day and hour.
+                for hour in 0..24 {
+                    span { class: "text-gray-500 dark:text-gray-400", aria_hidden: "true",
+                        if hour % 6 == 0 { "{hour}" }
+                    }
+                }
+                for (day, hours) in heatmap.iter().enumerate() {
+                    span { aria_hidden: "true", {t().day(day).initial} }
+                    for (hour, minutes) in hours.iter().copied().enumerate() {
+                        div {
+                            class: "h-3 rounded-sm",
+                            class: match (minutes * 4).div_ceil(max) {
+                                0 => "bg-gray-200 dark:bg-gray-700",
+                                1 => "bg-green-200",
+                                2 => "bg-green-400",
+                                3 => "bg-green-600",
+                                _ => "bg-green-800",
+                            },
+                            title: "{t().day(day).name} {hour:02}:00, {stats::format_minutes(minutes)}",
+                            role: "img",
+                            aria_label: "{t().day(day).name} {hour:02}:00, {stats::format_minutes(minutes)}",
+                        }
+                    }
+                }
+            }
+        }
+    )
+}
+
+/// Suggests a block window from when YouTube gets used the most, see `stats::suggest_block_window`.
+/// Applying it only fills in the form, it still needs saving.
+#[component]
+fn BlockWindowSuggestion(
+    daily_usage: BTreeMap<String, DayUsage>,
+    config_signal: Signal<Option<config::Config>>,
+) -> Element {
+    let mut hours = use_signal(|| 2u32);
+    let heatmap = stats::hourly_heatmap(&daily_usage, HEATMAP_WEEKS);
+    let suggestion = stats::suggest_block_window(&heatmap, hours());
+
+    rsx!(
+        div { class: "grid gap-2 mt-2",
+            div { class: "flex items-center gap-2",
+                label { class: "text-sm", r#for: "suggestion-hours", {t().suggestion_length} }
+                input {
+                    class: "rounded-md border border-input bg-background px-2 py-1 text-sm w-16",
+                    id: "suggestion-hours",
+                    r#type: "number",
+                    min: "1",
+                    max: "12",
+                    value: "{hours}",
+                    oninput: move |evt| {
+                        if let Ok(new_hours) = evt.value().parse::<u32>() {
+                            hours.set(new_hours.clamp(1, 12));
+                        }
+                    },
+                }
+            }
+            if let Some(suggestion) = suggestion {
+                p { class: "text-sm",
+                    {
+                        let days = (0..7)
+                            .filter(|i| suggestion.active_days & (1 << i) != 0)
+                            .map(|i| t().day(i).short)
+                            .collect::<Vec<_>>()
+                            .join(", ");
+                        fill(
+                            t().suggestion,
+                            &[
+                                ("start", &format!("{:02}", suggestion.start_hour)),
+                                ("end", &format!("{:02}", suggestion.end_hour)),
+                                ("days", &days),
+                                ("coverage", &suggestion.coverage),
+                                ("weeks", &HEATMAP_WEEKS),
+                            ],
+                        )
+                    }
+                }
+                button {
+                    class: "rounded-md border border-input px-2 py-1 text-sm",
+                    onclick: move |_| {
+                        let mut config = config_signal.write();
+                        let config = config.get_or_insert_default();
+                        config.block_time_start = suggestion.start_hour * 60;
+                        config.block_time_end = suggestion.end_hour * 60;
+                        config.active_days = suggestion.active_days;
+                    },
+                    {t().use_suggestion}
+                }
+            } else {
+                p { class: "text-xs text-gray-500 dark:text-gray-400", {t().not_enough_usage} }
+            }
+        }
+    )
+}
+
+/// The Save button, plus whatever's keeping it from saving: problems with the form (see
+/// `Config::problems`) or with the storage.
+#[component]
+fn SaveSettings(config_signal: Signal<Option<config::Config>>) -> Element {
+    let mut saving = use_signal(|| false);
+    let mut saved: Signal<Option<config::Config>> = use_signal(|| None);
+    let mut error: Signal<Option<String>> = use_signal(|| None);
+
+    let config = config_signal();
+    let problems: Vec<String> = match &config {
+        Some(config) => config.problems().iter().map(ToString::to_string).collect(),
+        None => vec![t().set_up_first.to_owned()],
+    };
+    let is_saved = config.is_some() && saved() == config;
+
+    let save = move |_| {
+        let Some(config) = config_signal() else {
+            return;
+        };
+        if !config.problems().is_empty() || saving() {
+            return;
+        }
+        saving.set(true);
+        error.set(None);
+        spawn(async move {
+            match config.flush_config().await {
+                Ok(()) => saved.set(Some(config)),
+                Err(e) => error.set(Some(fill(t().save_failed, &[("error", &e)]))),
+            }
+            saving.set(false);
+        });
+    };
+
+    rsx!(
+        // Shown as soon as there are any, same as the ones next to the inputs.
+        if !problems.is_empty() {
+            ul {
+                id: "save-problems",
+                class: "text-xs text-red-600 list-disc pl-4",
+                role: "alert",
+                for problem in problems {
+                    li { "{problem}" }
+                }
+            }
+        }
+        button {
+            class: "inline-flex items-center justify-center whitespace-nowrap rounded-md text-sm font-medium ring-offset-background transition-colors focus-visible:outline-none focus-visible:ring-2 focus-visible:ring-ring focus-visible:ring-offset-2 disabled:pointer-events-none disabled:opacity-50 bg-primary text-white hover:bg-primary/90 h-10 px-4 py-2 w-full",
+            class: if saving() { "opacity-50" },
+            // Rather than `disabled`, which would throw the keyboard focus out of the button.
+            aria_disabled: "{saving()}",
+            aria_describedby: "save-problems",
+            onclick: save,
+            if saving() { {t().saving} } else { {t().save} }
+        }
+        // Always there, same as the selector pack status.
+        div { role: "status", aria_live: "polite",
+            if let Some(error) = error() {
+                p { class: "text-xs text-red-600", "{error}" }
+            } else if is_saved {
+                p { class: "text-xs text-green-600", {t().saved} }
+            }
+        }
+    )
+}
+
 fn show_welcome_screen() -> Element {
-    show_settings(None)
+    show_settings(None, Default::default())
 }
 
-fn show_settings(storage: Option<config::Storage>) -> Element {
+fn show_settings(storage: Option<config::Storage>, usage: config::Usage) -> Element {
     // Idea for this page:
     // 1. Show the current settings (i.e start and end time)
     // 2. Allow the user to change the settings by reverting to the previous page.
     // 3. Show some statistics (hours of YouTube accessed today, etc.)
 
+    let daily_usage = usage.daily_usage;
+    let stored_pack = storage.as_ref().and_then(|s| s.selector_pack.clone());
     let config = storage.map(|s| s.user_config);
 
     let mut config_signal: Signal<Option<config::Config>> = use_signal(|| config);
+    // Same checks as in `Config::problems()`, for next to the inputs they're about.
+    let lead_time_problems = config_signal
+        .read()
+        .as_ref()
+        .map(|c| c.notifications.problems())
+        .unwrap_or_default();
+    let no_session_gap = config_signal
+        .read()
+        .as_ref()
+        .is_some_and(|c| c.problems().contains(&ConfigProblem::NoSessionGap));
 
     rsx!(
-        div { class: "rounded-lg border bg-card text-card-foreground shadow-sm w-full max-w-sm mx-auto",
+        div { class: "rounded-lg border dark:border-gray-700 bg-card text-card-foreground shadow-sm w-full max-w-sm mx-auto",
             div { class: "flex flex-col space-y-1.5 p-6",
                 h3 { class: "font-semibold whitespace-nowrap tracking-tight text-lg", "TouchGrass" }
                 p { class: "text-sm text-muted-foreground",
-                    "Control your YouTube usage with this extension."
+                    {t().tagline}
                 }
             }
             div { class: "p-6 grid gap-4",
@@ -89,15 +1135,17 @@ fn show_settings(storage: Option<config::Storage>) -> Element {
                             label {
                                 class: "text-sm font-medium leading-none peer-disabled:cursor-not-allowed peer-disabled:opacity-70",
                                 r#for: "start-time",
-                                "Start Time"
+                                {t().start_time}
                             }
                         }
                         div { class: "flex items-center",
                             input {
                                 class: "flex h-10 rounded-md border border-input bg-background mt-2 px-3 py-2 text-sm ring-offset-background file:border-0 file:bg-transparent file:text-sm file:font-medium placeholder:text-muted-foreground focus-visible:outline-none focus-visible:ring-2 focus-visible:ring-ring focus-visible:ring-offset-2 disabled:cursor-not-allowed disabled:opacity-50 w-23",
-                                class: if config_signal.read().is_none() { "red-border" },
+                                class: if config_signal.read().as_ref().is_none_or(|c| c.block_time_start == c.block_time_end) { "red-border" },
                                 id: "start-time",
                                 placeholder: "08",
+                                aria_invalid: "{config_signal.read().as_ref().is_none_or(|c| c.block_time_start == c.block_time_end)}",
+                                aria_describedby: "block-window-error",
                                 value: if let Some(start_time) = config_signal.read().as_ref().map(|c| c.block_time_start) {
                                     format!("{:02}:{:02}", start_time / 60, start_time % 60)
                                 },
@@ -117,15 +1165,17 @@ fn show_settings(storage: Option<config::Storage>) -> Element {
                             label {
                                 class: "text-sm font-medium leading-none peer-disabled:cursor-not-allowed peer-disabled:opacity-70",
                                 r#for: "end-time",
-                                "End Time"
+                                {t().end_time}
                             }
                         }
                         div { class: "flex items-center",
                             input {
                                 class: "flex h-10 rounded-md border border-input bg-background mt-2 px-3 py-2 text-sm ring-offset-background file:border-0 file:bg-transparent file:text-sm file:font-medium placeholder:text-muted-foreground focus-visible:outline-none focus-visible:ring-2 focus-visible:ring-ring focus-visible:ring-offset-2 disabled:cursor-not-allowed disabled:opacity-50 w-23",
-                                class: if config_signal.read().is_none() { "red-border" },
+                                class: if config_signal.read().as_ref().is_none_or(|c| c.block_time_start == c.block_time_end) { "red-border" },
                                 id: "end-time",
                                 placeholder: "17",
+                                aria_invalid: "{config_signal.read().as_ref().is_none_or(|c| c.block_time_start == c.block_time_end)}",
+                                aria_describedby: "block-window-error",
                                 value: if let Some(end_time) = config_signal.read().as_ref().map(|c| c.block_time_end) {
                                     format!("{:02}:{:02}", end_time / 60, end_time % 60)
                                 },
@@ -140,53 +1190,541 @@ fn show_settings(storage: Option<config::Storage>) -> Element {
                         }
                     }
                 }
+                if config_signal.read().as_ref().is_some_and(|c| c.block_time_start == c.block_time_end) {
+                    p { id: "block-window-error", class: "text-xs text-red-600", {t().same_start_end} }
+                }
+                div { class: "grid gap-2",
+                    p { class: "text-sm font-medium leading-none", {t().days_of_week} }
+                    WeekdayToggle {
+                        id: "youtube-days",
+                        active_days: config_signal.read().as_ref().map(|c| c.active_days).unwrap_or(0),
+                        label: t().youtube_block_days,
+                        on_change: move |active_days| {
+                            config_signal.write().get_or_insert_default().active_days = active_days;
+                            console_log!("Active days are now: {active_days:?}");
+                        },
+                    }
+                }
                 div {
                     label {
                         class: "text-sm font-medium leading-none peer-disabled:cursor-not-allowed peer-disabled:opacity-70",
-                        r#for: "day-selector",
-                        "Day of the week"
+                        r#for: "daily-budget",
+                        {t().daily_budget}
+                    }
+                    input {
+                        class: "flex h-10 w-full rounded-md border border-input bg-background mt-2 px-3 py-2 text-sm ring-offset-background placeholder:text-muted-foreground focus-visible:outline-none focus-visible:ring-2 focus-visible:ring-ring focus-visible:ring-offset-2",
+                        class: if config_signal.read().as_ref().and_then(|c| c.daily_budget).is_some_and(|budget| !(1..=config::MAX_DAILY_BUDGET).contains(&budget)) { "red-border" },
+                        id: "daily-budget",
+                        r#type: "number",
+                        min: "1",
+                        max: "{config::MAX_DAILY_BUDGET}",
+                        placeholder: t().no_limit,
+                        aria_invalid: "{config_signal.read().as_ref().and_then(|c| c.daily_budget).is_some_and(|budget| !(1..=config::MAX_DAILY_BUDGET).contains(&budget))}",
+                        aria_describedby: "daily-budget-error",
+                        value: if let Some(budget) = config_signal.read().as_ref().and_then(|c| c.daily_budget) {
+                            "{budget}"
+                        },
+                        oninput: move |evt| {
+                            // Empty (or garbage) means no budget at all.
+                            let budget = evt.value().parse::<u32>().ok();
+                            config_signal.write().get_or_insert_default().daily_budget = budget;
+                            console_log!("Daily budget is now: {budget:?}");
+                        },
+                    }
+                    if config_signal.read().as_ref().and_then(|c| c.daily_budget).is_some_and(|budget| !(1..=config::MAX_DAILY_BUDGET).contains(&budget)) {
+                        p { id: "daily-budget-error", class: "text-xs text-red-600 mt-1",
+                            {fill(t().budget_range, &[("max", &config::MAX_DAILY_BUDGET)])}
+                        }
+                    }
+                }
+                div {
+                    label {
+                        class: "text-sm font-medium leading-none peer-disabled:cursor-not-allowed peer-disabled:opacity-70",
+                        r#for: "on-block-selector",
+                        {t().when_blocked}
                     }
                     select {
                         class: "flex w-full rounded-md border border-input bg-background mt-2 px-3 py-2 text-sm ring-offset-background placeholder:text-muted-foreground focus-visible:outline-none focus-visible:ring-2 focus-visible:ring-ring focus-visible:ring-offset-2 disabled:cursor-not-allowed disabled:opacity-50",
-                        id: "day-selector",
-                        multiple: true,
+                        id: "on-block-selector",
                         onchange: move |evt| {
-                            let mut new_active_days = 0u8;
-                            for (day_str, _) in evt.data.values() {
-                                if let Ok(day_idx) = day_str.parse::<u8>() {
-                                    new_active_days |= 1 << day_idx;
-                                }
-                            }
-                            config_signal.write().get_or_insert_default().active_days = new_active_days;
-                            console_log!("Active days are now: {new_active_days:?}");
+                            let on_block = block_action_from_kind(&evt.value());
+                            console_log!("On-block action is now: {on_block:?}");
+                            config_signal.write().get_or_insert_default().on_block = on_block;
                         },
                         {
-                            let days_of_week = ["Monday", "Tuesday", "Wednesday", "Thursday", "Friday", "Saturday", "Sunday"];
-                            days_of_week.into_iter().enumerate().map(|(i, day)| rsx! {
+                            let curr_kind = block_action_kind(&config_signal.read().as_ref().map(|c| c.on_block.clone()).unwrap_or_default());
+                            BLOCK_ACTION_KINDS.into_iter().map(move |kind| rsx! {
                                 option {
-                                    value: "{i}",
-                                    selected: (config_signal.read().as_ref().map(|c| c.active_days).unwrap_or(0) & (1 << i)) != 0,
-                                    "{day}"
+                                    value: "{kind}",
+                                    selected: kind == curr_kind,
+                                    {block_action_label(kind)}
                                 }
                             })
                         }
                     }
+                    {
+                        let on_block = config_signal.read().as_ref().map(|c| c.on_block.clone()).unwrap_or_default();
+                        match on_block {
+                            BlockAction::Redirect(RedirectTarget::Playlist(ref s) | RedirectTarget::Url(ref s)) => {
+                                let (placeholder, label) = if matches!(on_block, BlockAction::Redirect(RedirectTarget::Playlist(_))) {
+                                    (t().playlist_id_placeholder, t().playlist_id_placeholder)
+                                } else {
+                                    ("https://...", t().redirect_url)
+                                };
+                                let problem = match &on_block {
+                                    BlockAction::Redirect(target) => target.problem(),
+                                    _ => None,
+                                };
+                                rsx! {
+                                    input {
+                                        class: "flex h-10 w-full rounded-md border border-input bg-background mt-2 px-3 py-2 text-sm ring-offset-background placeholder:text-muted-foreground focus-visible:outline-none focus-visible:ring-2 focus-visible:ring-ring focus-visible:ring-offset-2",
+                                        class: if problem.is_some() { "red-border" },
+                                        id: "redirect-target",
+                                        placeholder: placeholder,
+                                        aria_label: label,
+                                        aria_invalid: "{problem.is_some()}",
+                                        aria_describedby: "redirect-target-error",
+                                        value: "{s}",
+                                        oninput: move |evt| {
+                                            if let Some(config) = config_signal.write().as_mut() {
+                                                if let BlockAction::Redirect(
+                                                    RedirectTarget::Playlist(s) | RedirectTarget::Url(s),
+                                                ) = &mut config.on_block
+                                                {
+                                                    *s = evt.value();
+                                                }
+                                            }
+                                        },
+                                    }
+                                    if let Some(problem) = problem {
+                                        p { id: "redirect-target-error", class: "text-xs text-red-600 mt-1", {problem.message()} }
+                                    }
+                                }
+                            }
+                            _ => None,
+                        }
+                    }
                 }
-                button {
-                    class: "inline-flex items-center justify-center whitespace-nowrap rounded-md text-sm font-medium ring-offset-background transition-colors focus-visible:outline-none focus-visible:ring-2 focus-visible:ring-ring focus-visible:ring-offset-2 disabled:pointer-events-none disabled:opacity-50 bg-primary text-white hover:bg-primary/90 h-10 px-4 py-2 w-full",
-                    onclick: move |_| {
-                        spawn(async move {
-                            if let Some(config) = config_signal.read().as_ref() {
-                                config.flush_config().await.unwrap();
+                FilterRulesInput {
+                    id: "allow-filter",
+                    label: t().allow_filter,
+                    rules: config_signal.read().as_ref().map(|c| c.feed_filter.allow.clone()).unwrap_or_default(),
+                    on_change: move |rules| config_signal.write().get_or_insert_default().feed_filter.allow = rules,
+                }
+                FilterRulesInput {
+                    id: "deny-filter",
+                    label: t().deny_filter,
+                    rules: config_signal.read().as_ref().map(|c| c.feed_filter.deny.clone()).unwrap_or_default(),
+                    on_change: move |rules| config_signal.write().get_or_insert_default().feed_filter.deny = rules,
+                }
+                LinesInput {
+                    id: "allowed-channels",
+                    label: t().allowed_channels,
+                    placeholder: t().allowed_channels_placeholder,
+                    lines: config_signal.read().as_ref().map(|c| c.allowed_channels.clone()).unwrap_or_default(),
+                    on_change: move |channels| config_signal.write().get_or_insert_default().allowed_channels = channels,
+                }
+                div { class: "flex items-center gap-2",
+                    input {
+                        id: "hard-block",
+                        r#type: "checkbox",
+                        checked: config_signal.read().as_ref().map(|c| c.hard_block).unwrap_or(false),
+                        onchange: move |evt| {
+                            config_signal.write().get_or_insert_default().hard_block = evt.checked();
+                        },
+                    }
+                    label { class: "text-sm", r#for: "hard-block",
+                        {t().hard_block}
+                    }
+                }
+                details {
+                    summary { class: "text-sm font-medium cursor-pointer", {t().suggest_block_window} }
+                    BlockWindowSuggestion { daily_usage: daily_usage.clone(), config_signal }
+                }
+                fieldset { class: "grid gap-2",
+                    legend { class: "text-sm font-medium leading-none mb-2", {t().session_limit} }
+                    label { class: "text-sm", r#for: "session-minutes", {t().session_minutes} }
+                    input {
+                        class: "flex h-10 w-full rounded-md border border-input bg-background px-3 py-2 text-sm ring-offset-background placeholder:text-muted-foreground focus-visible:outline-none focus-visible:ring-2 focus-visible:ring-ring focus-visible:ring-offset-2",
+                        id: "session-minutes",
+                        r#type: "number",
+                        min: "1",
+                        placeholder: t().no_limit,
+                        value: if let Some(minutes) = config_signal.read().as_ref().and_then(|c| c.session_limit.minutes) {
+                            "{minutes}"
+                        },
+                        oninput: move |evt| {
+                            let minutes = evt.value().parse::<u32>().ok().filter(|minutes| *minutes > 0);
+                            config_signal.write().get_or_insert_default().session_limit.minutes = minutes;
+                        },
+                    }
+                    label { class: "text-sm", r#for: "session-gap", {t().session_gap} }
+                    input {
+                        class: "flex h-10 w-full rounded-md border border-input bg-background px-3 py-2 text-sm ring-offset-background placeholder:text-muted-foreground focus-visible:outline-none focus-visible:ring-2 focus-visible:ring-ring focus-visible:ring-offset-2",
+                        class: if no_session_gap { "red-border" },
+                        id: "session-gap",
+                        r#type: "number",
+                        min: "1",
+                        aria_invalid: "{no_session_gap}",
+                        aria_describedby: "session-gap-error",
+                        value: "{config_signal.read().as_ref().map(|c| c.session_limit).unwrap_or_default().min_gap}",
+                        oninput: move |evt| {
+                            if let Ok(min_gap) = evt.value().parse::<u32>() {
+                                config_signal.write().get_or_insert_default().session_limit.min_gap = min_gap.max(1);
                             }
-                        });
-                    },
-                    "Save"
+                        },
+                    }
+                    if no_session_gap {
+                        p { id: "session-gap-error", class: "text-xs text-red-600", {ConfigProblem::NoSessionGap.to_string()} }
+                    }
+                }
+                fieldset { class: "grid gap-2",
+                    legend { class: "text-sm font-medium leading-none mb-2", {t().friction} }
+                    div { class: "flex items-center gap-2",
+                        input {
+                            id: "friction-autoplay",
+                            r#type: "checkbox",
+                            checked: config_signal.read().as_ref().is_some_and(|c| c.friction.disable_autoplay),
+                            onchange: move |evt| {
+                                config_signal.write().get_or_insert_default().friction.disable_autoplay = evt.checked();
+                            },
+                        }
+                        label { class: "text-sm", r#for: "friction-autoplay", {t().keep_autoplay_off} }
+                    }
+                    div { class: "flex items-center gap-2",
+                        input {
+                            id: "friction-pause-on-blur",
+                            r#type: "checkbox",
+                            checked: config_signal.read().as_ref().is_some_and(|c| c.friction.pause_on_blur),
+                            onchange: move |evt| {
+                                config_signal.write().get_or_insert_default().friction.pause_on_blur = evt.checked();
+                            },
+                        }
+                        label { class: "text-sm", r#for: "friction-pause-on-blur", {t().pause_on_blur} }
+                    }
+                    label { class: "text-sm", r#for: "friction-scroll-limit", {t().scroll_limit} }
+                    input {
+                        class: "flex h-10 w-full rounded-md border border-input bg-background px-3 py-2 text-sm ring-offset-background placeholder:text-muted-foreground focus-visible:outline-none focus-visible:ring-2 focus-visible:ring-ring focus-visible:ring-offset-2",
+                        id: "friction-scroll-limit",
+                        r#type: "number",
+                        min: "1",
+                        placeholder: t().never_ask,
+                        value: if let Some(pages) = config_signal.read().as_ref().and_then(|c| c.friction.scroll_page_limit) {
+                            "{pages}"
+                        },
+                        oninput: move |evt| {
+                            // Empty (or garbage) means no limit, same as the daily budget.
+                            let pages = evt.value().parse::<u32>().ok().filter(|pages| *pages > 0);
+                            config_signal.write().get_or_insert_default().friction.scroll_page_limit = pages;
+                        },
+                    }
+                }
+                fieldset { class: "grid gap-2",
+                    legend { class: "text-sm font-medium leading-none mb-2", {t().shorts} }
+                    div { class: "flex items-center gap-2",
+                        input {
+                            id: "shorts-hide-shelves",
+                            r#type: "checkbox",
+                            checked: config_signal.read().as_ref().is_some_and(|c| c.shorts.hide_shelves),
+                            onchange: move |evt| {
+                                config_signal.write().get_or_insert_default().shorts.hide_shelves = evt.checked();
+                            },
+                        }
+                        label { class: "text-sm", r#for: "shorts-hide-shelves", {t().hide_shorts_shelves} }
+                    }
+                    div { class: "flex items-center gap-2",
+                        input {
+                            id: "shorts-hide-sidebar",
+                            r#type: "checkbox",
+                            checked: config_signal.read().as_ref().is_some_and(|c| c.shorts.hide_sidebar_entry),
+                            onchange: move |evt| {
+                                config_signal.write().get_or_insert_default().shorts.hide_sidebar_entry = evt.checked();
+                            },
+                        }
+                        label { class: "text-sm", r#for: "shorts-hide-sidebar", {t().hide_shorts_sidebar} }
+                    }
+                    label { class: "text-sm", r#for: "shorts-page-action", {t().when_opening_short} }
+                    select {
+                        class: "flex w-full rounded-md border border-input bg-background px-3 py-2 text-sm ring-offset-background focus-visible:outline-none focus-visible:ring-2 focus-visible:ring-ring focus-visible:ring-offset-2",
+                        id: "shorts-page-action",
+                        onchange: move |evt| {
+                            let action = match evt.value().as_str() {
+                                "watch-page" => ShortsPageAction::WatchPage,
+                                "block" => ShortsPageAction::Block,
+                                _ => ShortsPageAction::Allow,
+                            };
+                            config_signal.write().get_or_insert_default().shorts.on_shorts_page = action;
+                        },
+                        {
+                            let curr = config_signal.read().as_ref().map(|c| c.shorts.on_shorts_page).unwrap_or_default();
+                            [
+                                ("allow", ShortsPageAction::Allow, t().short_allow),
+                                ("watch-page", ShortsPageAction::WatchPage, t().short_watch_page),
+                                ("block", ShortsPageAction::Block, t().short_block),
+                            ]
+                            .into_iter()
+                            .map(move |(value, action, desc)| rsx! {
+                                option { value: "{value}", selected: action == curr, "{desc}" }
+                            })
+                        }
+                    }
+                }
+                fieldset { class: "grid gap-2",
+                    legend { class: "text-sm font-medium leading-none mb-2", {t().dull_mode} }
+                    DullModeWindows { config_signal }
+                    div { class: "flex items-center gap-2",
+                        input {
+                            id: "dull-grayscale",
+                            r#type: "checkbox",
+                            checked: config_signal.read().as_ref().map(|c| c.dull_mode.grayscale).unwrap_or(true),
+                            onchange: move |evt| {
+                                config_signal.write().get_or_insert_default().dull_mode.grayscale = evt.checked();
+                            },
+                        }
+                        label { class: "text-sm", r#for: "dull-grayscale", {t().grayscale} }
+                    }
+                    div { class: "flex items-center gap-2",
+                        input {
+                            id: "dull-view-counts",
+                            r#type: "checkbox",
+                            checked: config_signal.read().as_ref().map(|c| c.dull_mode.hide_view_counts).unwrap_or(true),
+                            onchange: move |evt| {
+                                config_signal.write().get_or_insert_default().dull_mode.hide_view_counts = evt.checked();
+                            },
+                        }
+                        label { class: "text-sm", r#for: "dull-view-counts", {t().hide_view_counts} }
+                    }
+                    div { class: "flex items-center gap-2",
+                        input {
+                            id: "dull-titles",
+                            r#type: "checkbox",
+                            checked: config_signal.read().as_ref().map(|c| c.dull_mode.tame_titles).unwrap_or(true),
+                            onchange: move |evt| {
+                                config_signal.write().get_or_insert_default().dull_mode.tame_titles = evt.checked();
+                            },
+                        }
+                        label { class: "text-sm", r#for: "dull-titles", {t().tame_titles} }
+                    }
+                    label { class: "text-sm", r#for: "dull-thumbnails", {t().thumbnails} }
+                    select {
+                        class: "flex w-full rounded-md border border-input bg-background px-3 py-2 text-sm ring-offset-background focus-visible:outline-none focus-visible:ring-2 focus-visible:ring-ring focus-visible:ring-offset-2",
+                        id: "dull-thumbnails",
+                        onchange: move |evt| {
+                            let style = match evt.value().as_str() {
+                                "normal" => ThumbnailStyle::Normal,
+                                "hide" => ThumbnailStyle::Hide,
+                                _ => ThumbnailStyle::Blur,
+                            };
+                            config_signal.write().get_or_insert_default().dull_mode.thumbnails = style;
+                        },
+                        {
+                            let curr = config_signal.read().as_ref().map(|c| c.dull_mode.thumbnails).unwrap_or_default();
+                            [
+                                ("normal", ThumbnailStyle::Normal, t().thumbnails_leave),
+                                ("blur", ThumbnailStyle::Blur, t().thumbnails_blur),
+                                ("hide", ThumbnailStyle::Hide, t().thumbnails_hide),
+                            ]
+                            .into_iter()
+                            .map(move |(value, style, desc)| rsx! {
+                                option { value: "{value}", selected: style == curr, "{desc}" }
+                            })
+                        }
+                    }
+                }
+                fieldset { class: "grid gap-2",
+                    legend { class: "text-sm font-medium leading-none mb-2", {t().notifications} }
+                    div { class: "flex items-center gap-2",
+                        input {
+                            id: "notify-block-window",
+                            r#type: "checkbox",
+                            checked: config_signal.read().as_ref().map(|c| c.notifications.before_block_window).unwrap_or(true),
+                            onchange: move |evt| {
+                                config_signal.write().get_or_insert_default().notifications.before_block_window = evt.checked();
+                            },
+                        }
+                        label { class: "text-sm", r#for: "notify-block-window", {t().notify_before_window} }
+                    }
+                    div { class: "flex items-center gap-2",
+                        input {
+                            id: "notify-budget",
+                            r#type: "checkbox",
+                            checked: config_signal.read().as_ref().map(|c| c.notifications.before_budget_runs_out).unwrap_or(true),
+                            onchange: move |evt| {
+                                config_signal.write().get_or_insert_default().notifications.before_budget_runs_out = evt.checked();
+                            },
+                        }
+                        label { class: "text-sm", r#for: "notify-budget", {t().notify_before_budget} }
+                    }
+                    label { class: "text-sm", r#for: "notify-lead-times", {t().lead_times} }
+                    input {
+                        class: "flex h-10 w-full rounded-md border border-input bg-background px-3 py-2 text-sm ring-offset-background placeholder:text-muted-foreground focus-visible:outline-none focus-visible:ring-2 focus-visible:ring-ring focus-visible:ring-offset-2",
+                        class: if !lead_time_problems.is_empty() { "red-border" },
+                        id: "notify-lead-times",
+                        placeholder: "10, 2",
+                        aria_invalid: "{!lead_time_problems.is_empty()}",
+                        aria_describedby: "notify-lead-times-error",
+                        initial_value: config_signal.read().as_ref().map(|c| c.notifications.clone()).unwrap_or_default()
+                            .lead_times.iter().map(u32::to_string).collect::<Vec<_>>().join(", "),
+                        oninput: move |evt| {
+                            let lead_times: Vec<u32> = evt
+                                .value()
+                                .split(',')
+                                .filter_map(|lead| lead.trim().parse().ok())
+                                .collect();
+                            console_log!("Notification lead times are now: {lead_times:?}");
+                            config_signal.write().get_or_insert_default().notifications.lead_times = lead_times;
+                        },
+                    }
+                    if !lead_time_problems.is_empty() {
+                        div { id: "notify-lead-times-error", class: "text-xs text-red-600",
+                            for problem in lead_time_problems.iter() {
+                                p { "{problem}" }
+                            }
+                        }
+                    }
+                }
+                fieldset { class: "grid gap-2",
+                    legend { class: "text-sm font-medium leading-none mb-2", {t().weekly_report} }
+                    label { class: "text-sm", r#for: "report-delivery", {t().report_when} }
+                    select {
+                        class: "flex w-full rounded-md border border-input bg-background px-3 py-2 text-sm ring-offset-background focus-visible:outline-none focus-visible:ring-2 focus-visible:ring-ring focus-visible:ring-offset-2",
+                        id: "report-delivery",
+                        onchange: move |evt| {
+                            let delivery = match evt.value().as_str() {
+                                "off" => ReportDelivery::Off,
+                                "open-tab" => ReportDelivery::OpenTab,
+                                _ => ReportDelivery::Notification,
+                            };
+                            config_signal.write().get_or_insert_default().weekly_report.delivery = delivery;
+                        },
+                        {
+                            let curr = config_signal.read().as_ref().map(|c| c.weekly_report.delivery).unwrap_or_default();
+                            [
+                                ("notification", ReportDelivery::Notification, t().report_notification),
+                                ("open-tab", ReportDelivery::OpenTab, t().report_open_tab),
+                                ("off", ReportDelivery::Off, t().report_off),
+                            ]
+                            .into_iter()
+                            .map(move |(value, delivery, desc)| rsx! {
+                                option { value: "{value}", selected: delivery == curr, "{desc}" }
+                            })
+                        }
+                    }
+                    label { class: "text-sm", r#for: "report-weekday", {t().report_weekday} }
+                    select {
+                        class: "flex w-full rounded-md border border-input bg-background px-3 py-2 text-sm ring-offset-background focus-visible:outline-none focus-visible:ring-2 focus-visible:ring-ring focus-visible:ring-offset-2",
+                        id: "report-weekday",
+                        onchange: move |evt| {
+                            if let Ok(weekday) = evt.value().parse::<u8>() {
+                                config_signal.write().get_or_insert_default().weekly_report.weekday = weekday;
+                            }
+                        },
+                        {
+                            let curr = config_signal.read().as_ref().map(|c| c.weekly_report.weekday).unwrap_or_default();
+                            (0..7).map(move |i| rsx! {
+                                option { value: "{i}", selected: usize::from(curr) == i, {t().day(i).name} }
+                            })
+                        }
+                    }
+                    button {
+                        class: "rounded-md border border-input px-2 py-1 text-sm",
+                        onclick: move |_| {
+                            spawn(report::open());
+                        },
+                        {t().open_report}
+                    }
+                }
+                div { class: "grid gap-2",
+                    label { class: "text-sm font-medium leading-none", r#for: "theme", {t().theme} }
+                    select {
+                        class: "flex w-full rounded-md border border-input bg-background px-3 py-2 text-sm ring-offset-background focus-visible:outline-none focus-visible:ring-2 focus-visible:ring-ring focus-visible:ring-offset-2",
+                        id: "theme",
+                        onchange: move |evt| {
+                            let theme = match evt.value().as_str() {
+                                "light" => Theme::Light,
+                                "dark" => Theme::Dark,
+                                _ => Theme::System,
+                            };
+                            // Right away, as a preview. It's only kept once saved.
+                            theme::apply(theme);
+                            config_signal.write().get_or_insert_default().theme = theme;
+                        },
+                        {
+                            let curr = config_signal.read().as_ref().map(|c| c.theme).unwrap_or_default();
+                            [
+                                ("system", Theme::System, t().theme_system),
+                                ("light", Theme::Light, t().theme_light),
+                                ("dark", Theme::Dark, t().theme_dark),
+                            ]
+                            .into_iter()
+                            .map(move |(value, theme, desc)| rsx! {
+                                option { value: "{value}", selected: theme == curr, "{desc}" }
+                            })
+                        }
+                    }
+                }
+                div { class: "grid gap-2",
+                    label { class: "text-sm font-medium leading-none", r#for: "language", {t().language} }
+                    select {
+                        class: "flex w-full rounded-md border border-input bg-background px-3 py-2 text-sm ring-offset-background focus-visible:outline-none focus-visible:ring-2 focus-visible:ring-ring focus-visible:ring-offset-2",
+                        id: "language",
+                        onchange: move |evt| {
+                            let language = match evt.value().as_str() {
+                                "en" => Language::English,
+                                "hi" => Language::Hindi,
+                                _ => Language::Auto,
+                            };
+                            // Unlike the theme, no preview: it kicks in once saved and the popup's
+                            // opened again.
+                            config_signal.write().get_or_insert_default().language = language;
+                        },
+                        {
+                            let curr = config_signal.read().as_ref().map(|c| c.language).unwrap_or_default();
+                            // Each language by its own name, so it can be found without reading the
+                            // current one.
+                            [
+                                ("auto", Language::Auto, t().language_auto),
+                                ("en", Language::English, "English"),
+                                ("hi", Language::Hindi, "हिन्दी"),
+                            ]
+                            .into_iter()
+                            .map(move |(value, language, desc)| rsx! {
+                                option { value: "{value}", selected: language == curr, "{desc}" }
+                            })
+                        }
+                    }
+                }
+                details {
+                    summary { class: "text-sm font-medium cursor-pointer", {t().other_sites} }
+                    div { class: "grid gap-4 mt-2",
+                        for site in Site::ALL.into_iter().filter(|site| *site != Site::YouTube) {
+                            SiteScheduleEditor {
+                                key: "{site:?}",
+                                id: format!("{site:?}").to_lowercase(),
+                                name: site.name(),
+                                site_config: config_signal.read().as_ref().and_then(|c| c.sites.get(&site).cloned()).unwrap_or_default(),
+                                on_change: move |site_config| {
+                                    config_signal.write().get_or_insert_default().sites.insert(site, site_config);
+                                },
+                            }
+                        }
+                    }
+                }
+                details {
+                    summary { class: "text-sm font-medium cursor-pointer", {t().custom_sites} }
+                    CustomSitesEditor { config_signal }
+                }
+                details {
+                    summary { class: "text-sm font-medium cursor-pointer", {t().youtube_selectors} }
+                    SelectorPackSettings { config_signal, stored_pack: stored_pack.clone() }
                 }
+                SaveSettings { config_signal }
             }
+            UsageStats { daily_usage: daily_usage.clone() }
+            Streaks { daily_usage, daily_budget: config_signal.read().as_ref().and_then(|c| c.daily_budget) }
             div { class: "flex items-center p-6",
                 p { class: "text-xs text-gray-500 dark:text-gray-400",
-                    "YouTube will be disabled between the specified times."
+                    {t().footer}
                 }
             }
         }
@@ -1 +1,4 @@
 pub mod homepage;
+pub mod report;
+mod theme;
+mod weekday_toggle;
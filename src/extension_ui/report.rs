@@ -0,0 +1,163 @@
+use super::theme;
+use crate::{
+    config,
+    i18n::{self, fill, t},
+    report::{self, WeekReport},
+    stats::{self, format_minutes},
+};
+use dioxus::prelude::*;
+use wasm_bindgen::prelude::*;
+
+const BUSIEST: usize = 3;
+
+#[wasm_bindgen]
+pub fn start_report() {
+    console_error_panic_hook::set_once();
+    dioxus::launch(report_app)
+}
+
+fn report_app() -> Element {
+    // 1 = the last full week, which is what the report is about when it opens by itself.
+    let mut weeks_ago = use_signal(|| 1u32);
+    let usage = use_resource(|| async {
+        let config = config::get_storage()
+            .await
+            .map(|storage| storage.user_config)
+            .unwrap_or_default();
+        theme::apply(config.theme);
+        i18n::set_language(config.language);
+        i18n::apply_to_page();
+        config::get_usage().await
+    });
+
+    let page = match &*usage.read() {
+        None => rsx! { {t().loading} },
+        Some(Err(e)) => rsx! { h3 { {fill(t().report_load_failed, &[("error", e)])} } },
+        Some(Ok(usage)) => {
+            let report = report::week_report(&usage.daily_usage, weeks_ago());
+            rsx! {
+                div { class: "flex flex-row justify-between items-center gap-4",
+                    button {
+                        class: "rounded-md border border-input px-2 py-1 text-sm disabled:opacity-50",
+                        // Nothing's kept from before then.
+                        disabled: weeks_ago() + 1 >= config::USAGE_KEPT_WEEKS,
+                        onclick: move |_| weeks_ago += 1,
+                        {t().previous_week}
+                    }
+                    // Announced when the buttons either side change the week.
+                    h2 { class: "font-semibold tracking-tight", aria_live: "polite",
+                        if weeks_ago() == 0 {
+                            {t().this_week_so_far}
+                        } else {
+                            {fill(t().week_range, &[("first", &report.first_day), ("last", &report.last_day)])}
+                        }
+                    }
+                    button {
+                        class: "rounded-md border border-input px-2 py-1 text-sm disabled:opacity-50",
+                        disabled: weeks_ago() == 0,
+                        onclick: move |_| weeks_ago -= 1,
+                        {t().next_week}
+                    }
+                }
+                WeekSummary { report }
+            }
+        }
+    };
+
+    rsx! {
+        link { rel: "stylesheet", href: "./output.css" }
+        div { class: "rounded-lg border dark:border-gray-700 bg-card text-card-foreground shadow-sm w-full max-w-xl mx-auto my-8 p-6 grid gap-6",
+            h1 { class: "font-semibold tracking-tight text-lg", {t().report_title} }
+            {page}
+        }
+    }
+}
+
+#[component]
+fn WeekSummary(report: WeekReport) -> Element {
+    let change = |template: &str, difference: u32| {
+        fill(
+            template,
+            &[
+                ("difference", &format_minutes(difference)),
+                ("percent", &(difference * 100 / report.previous_total)),
+            ],
+        )
+    };
+    let comparison = match report.total.cmp(&report.previous_total) {
+        _ if report.previous_total == 0 => t().nothing_week_before.to_owned(),
+        std::cmp::Ordering::Equal => t().same_as_week_before.to_owned(),
+        std::cmp::Ordering::Greater => change(
+            t().more_than_week_before,
+            report.total - report.previous_total,
+        ),
+        std::cmp::Ordering::Less => change(
+            t().less_than_week_before,
+            report.previous_total - report.total,
+        ),
+    };
+    let max_day = report.days.iter().copied().max().unwrap_or(0).max(1);
+    let busiest_days = report.busiest_days(BUSIEST);
+    let busiest_hours = report.busiest_hours(BUSIEST);
+
+    rsx! {
+        div {
+            p { class: "text-3xl font-semibold", {format_minutes(report.total)} }
+            p { class: "text-sm text-gray-500 dark:text-gray-400", {fill(t().on_youtube, &[("comparison", &comparison)])} }
+        }
+        div { class: "grid grid-cols-2 gap-4 text-sm",
+            div {
+                p { class: "text-2xl font-semibold", "{report.blocks}" }
+                p { {t().times_blocked} }
+            }
+            div {
+                p { class: "text-2xl font-semibold", "{report.session_extensions}" }
+                p { {t().session_extensions} }
+            }
+        }
+        div { class: "grid gap-1",
+            h3 { class: "text-sm font-medium", {t().by_day} }
+            for (day, minutes) in report.days.iter().copied().enumerate() {
+                div { class: "grid grid-cols-[6rem_1fr_4rem] items-center gap-2 text-sm",
+                    span { {t().day(day).name} }
+                    div { class: "h-3 rounded-sm bg-green-500", style: "width: {minutes * 100 / max_day}%" }
+                    span { class: "text-right whitespace-nowrap", {format_minutes(minutes)} }
+                }
+            }
+            if !busiest_days.is_empty() {
+                p { class: "text-sm mt-1",
+                    {t().busiest}
+                    {busiest_days.iter().map(|(day, _)| t().day(*day).name).collect::<Vec<_>>().join(", ")}
+                }
+            }
+        }
+        div {
+            h3 { class: "text-sm font-medium", {t().busiest_hours} }
+            if busiest_hours.is_empty() {
+                p { class: "text-xs text-gray-500 dark:text-gray-400", {t().no_hourly_data} }
+            }
+            ol { class: "text-sm",
+                for (hour, minutes) in busiest_hours {
+                    li { class: "flex justify-between gap-2",
+                        span { {fill(t().hour_range, &[("start", &format!("{hour:02}")), ("end", &format!("{:02}", (hour + 1) % 24))])} }
+                        span { class: "whitespace-nowrap", {format_minutes(minutes)} }
+                    }
+                }
+            }
+        }
+        div {
+            h3 { class: "text-sm font-medium", {t().top_channels} }
+            if report.top_channels.is_empty() {
+                p { class: "text-xs text-gray-500 dark:text-gray-400", {t().nothing_yet} }
+            }
+            ol { class: "text-sm",
+                for (name, minutes) in report.top_channels.clone() {
+                    li { class: "flex justify-between gap-2",
+                        span { class: "truncate", title: "{name}", "{name}" }
+                        span { class: "whitespace-nowrap", {stats::format_minutes(minutes)} }
+                    }
+                }
+            }
+        }
+    }
+}
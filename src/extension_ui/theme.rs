@@ -0,0 +1,14 @@
+//! Light/dark for the extension's own pages. The `dark:` variants are on Tailwind's class strategy,
+//! so it all comes down to whether <html> has the `dark` class.
+
+use crate::config::Theme;
+
+pub fn apply(theme: Theme) {
+    let Some(root) = web_sys::window()
+        .and_then(|window| window.document())
+        .and_then(|document| document.document_element())
+    else {
+        return;
+    };
+    let _ = root.class_list().toggle_with_force("dark", theme.is_dark());
+}
@@ -0,0 +1,73 @@
+//! Pill buttons for picking days of the week, in place of a `<select multiple>` (which a stray click
+//! resets to a single day).
+
+use crate::i18n::t;
+use dioxus::prelude::*;
+
+const WEEKDAYS: u8 = 0b001_1111;
+const WEEKENDS: u8 = 0b110_0000;
+const EVERY_DAY: u8 = 0b111_1111;
+
+/// Edits an `active_days` bitmap (bit 0 = Monday). Plain buttons with `aria-pressed`, so they're
+/// tabbable and toggle with Enter/Space like any other button.
+#[component]
+pub fn WeekdayToggle(
+    // For the error message's ID.
+    id: String,
+    // ReadOnlySignal so that the click handlers always see the latest bitmap.
+    active_days: ReadOnlySignal<u8>,
+    on_change: EventHandler<u8>,
+    // What the days are for, for screen readers.
+    label: String,
+) -> Element {
+    let days = active_days() & EVERY_DAY;
+    let preset_class = |preset: u8| {
+        if days == preset {
+            "rounded-full px-2 py-0.5 text-xs bg-primary text-white"
+        } else {
+            "rounded-full px-2 py-0.5 text-xs border border-input"
+        }
+    };
+
+    rsx!(
+        div { class: "grid gap-1",
+            div {
+                class: "flex flex-row gap-1 rounded-full border border-transparent",
+                class: if days == 0 { "red-border" },
+                role: "group",
+                aria_label: "{label}",
+                aria_describedby: "{id}-error",
+                for (i, day) in (0..7).map(|i| (i, t().day(i))) {
+                    button {
+                        r#type: "button",
+                        class: "rounded-full w-9 py-1 text-xs font-medium focus-visible:outline-none focus-visible:ring-2 focus-visible:ring-ring",
+                        class: if days & (1 << i) != 0 { "bg-primary text-white" } else { "border border-input" },
+                        title: "{day.name}",
+                        aria_label: "{day.name}",
+                        aria_pressed: "{days & (1 << i) != 0}",
+                        onclick: move |_| on_change.call(active_days() ^ (1 << i)),
+                        {day.short}
+                    }
+                }
+            }
+            div { class: "flex flex-row gap-1",
+                for (name, preset) in [
+                    (t().preset_weekdays, WEEKDAYS),
+                    (t().preset_weekends, WEEKENDS),
+                    (t().preset_every_day, EVERY_DAY),
+                ] {
+                    button {
+                        r#type: "button",
+                        class: preset_class(preset),
+                        aria_pressed: "{days == preset}",
+                        onclick: move |_| on_change.call(preset),
+                        "{name}"
+                    }
+                }
+            }
+            if days == 0 {
+                p { id: "{id}-error", class: "text-xs text-red-600", {t().no_days_picked} }
+            }
+        }
+    )
+}
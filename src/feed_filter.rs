@@ -0,0 +1,155 @@
+use crate::config::{FeedFilter, FilterRules};
+use crate::console_log;
+use crate::sites::{MediaInfo, SiteAdapter};
+use wasm_bindgen::prelude::*;
+
+#[wasm_bindgen]
+extern "C" {
+    // js_sys::RegExp::new() doesn't catch, so an invalid user-supplied pattern would throw straight
+    // through us. This binding hands the exception back instead.
+    #[wasm_bindgen(js_name = RegExp)]
+    type FallibleRegExp;
+
+    #[wasm_bindgen(catch, constructor, js_class = "RegExp")]
+    fn new(pattern: &str, flags: &str) -> Result<FallibleRegExp, JsValue>;
+}
+
+fn try_new_regexp(pattern: &str, flags: &str) -> Result<js_sys::RegExp, JsValue> {
+    FallibleRegExp::new(pattern, flags).map(|regex| regex.unchecked_into())
+}
+
+pub fn is_valid_regex(pattern: &str) -> bool {
+    try_new_regexp(pattern, "i").is_ok()
+}
+
+/// `FilterRules`, but with the regexes already compiled (and the strings lowercased) so that we
+/// don't redo it for every single card.
+struct CompiledRules {
+    keywords: Vec<String>,
+    regexes: Vec<js_sys::RegExp>,
+    channels: Vec<String>,
+}
+
+impl CompiledRules {
+    fn new(rules: &FilterRules) -> Self {
+        let regexes = rules
+            .regexes
+            .iter()
+            .filter_map(|pattern| match try_new_regexp(pattern, "i") {
+                Ok(regex) => Some(regex),
+                Err(e) => {
+                    console_log!("Skipping invalid regex {pattern:?}: {e:?}");
+                    None
+                }
+            })
+            .collect();
+
+        Self {
+            keywords: rules.keywords.iter().map(|k| k.to_lowercase()).collect(),
+            regexes,
+            channels: rules
+                .channels
+                .iter()
+                .map(|c| normalize_channel(c))
+                .collect(),
+        }
+    }
+
+    fn is_empty(&self) -> bool {
+        self.keywords.is_empty() && self.regexes.is_empty() && self.channels.is_empty()
+    }
+
+    fn matches(&self, video: &MediaInfo) -> bool {
+        let title = video.title.to_lowercase();
+        let channel_name = video.channel_name.as_deref().map(normalize_channel);
+        let channel_handle = video.channel_handle.as_deref().map(normalize_channel);
+
+        self.keywords.iter().any(|keyword| title.contains(keyword))
+            || self.regexes.iter().any(|regex| regex.test(&video.title))
+            || self.channels.iter().any(|channel| {
+                Some(channel) == channel_name.as_ref() || Some(channel) == channel_handle.as_ref()
+            })
+    }
+}
+
+// Makes "@Foo", "foo" and " Foo " all compare equal.
+pub fn normalize_channel(channel: &str) -> String {
+    channel.trim().trim_start_matches('@').to_lowercase()
+}
+
+struct CompiledFilter {
+    allow: CompiledRules,
+    deny: CompiledRules,
+}
+
+impl CompiledFilter {
+    fn should_hide(&self, video: &MediaInfo) -> bool {
+        self.deny.matches(video) || !(self.allow.is_empty() || self.allow.matches(video))
+    }
+}
+
+/**
+ * YouTube keeps loading in more cards as you scroll (and recycles existing card elements for
+ * different videos on navigation), so a one-time pass isn't enough. Rather than tracking every
+ * mutation, we just re-check all the cards every second; there's only a few dozen of them at a time.
+ */
+pub fn start_filtering(
+    window: &web_sys::Window,
+    adapter: &'static dyn SiteAdapter,
+    filter: &FeedFilter,
+) {
+    if filter.is_empty() || adapter.card_selector().is_none() {
+        return;
+    }
+
+    let filter = CompiledFilter {
+        allow: CompiledRules::new(&filter.allow),
+        deny: CompiledRules::new(&filter.deny),
+    };
+    let document = window.document().expect("should have a document on window");
+
+    let closure = Closure::<dyn Fn()>::new(move || apply_filter(&document, adapter, &filter));
+
+    window
+        .set_interval_with_callback_and_timeout_and_arguments_0(
+            closure.as_ref().unchecked_ref(),
+            1000,
+        )
+        .expect("Failed to setInterval the feed filter.");
+
+    // Same reasoning as in `record_watch_time`: lives as long as the page does.
+    closure.forget();
+}
+
+fn apply_filter(document: &web_sys::Document, adapter: &dyn SiteAdapter, filter: &CompiledFilter) {
+    let Some(card_selector) = adapter.card_selector() else {
+        return;
+    };
+    let Ok(cards) = document.query_selector_all(&card_selector) else {
+        return;
+    };
+
+    for i in 0..cards.length() {
+        let Some(card) = cards
+            .item(i)
+            .and_then(|node| node.dyn_into::<web_sys::HtmlElement>().ok())
+        else {
+            continue;
+        };
+        // Cards that haven't been populated yet come back as None. Leave them alone; we'll get them
+        // on the next pass.
+        let Some(video) = adapter.card_info(&card) else {
+            continue;
+        };
+
+        // Inline style rather than the `hidden` attribute, as YouTube's own CSS sets `display` on
+        // these elements. Toggle rather than only hide: a recycled card may now hold a video
+        // that's allowed.
+        let style = card.style();
+        let _ = if filter.should_hide(&video) {
+            style.set_property("display", "none")
+        } else {
+            style.remove_property("display").map(|_| ())
+        };
+    }
+}
@@ -0,0 +1,162 @@
+//! Hurdles for the bits that keep you watching/scrolling without deciding to: autoplay, endless
+//! scrolling, and videos playing on in a tab you've already left. Runs next to `remove_distractions`,
+//! but all day rather than just in the block window.
+
+use crate::config::FrictionSettings;
+use crate::i18n::{fill, t};
+use crate::sites::SiteAdapter;
+use crate::{console_log, content_script, selector_pack};
+use std::cell::{Cell, RefCell};
+use wasm_bindgen::prelude::*;
+
+const LOAD_MORE_PROMPT_ID: &str = "touch-grass-load-more";
+
+thread_local! {
+    // The page (path + query) the scroll limit is counting for. Navigating elsewhere starts over.
+    static SCROLL_PAGE: RefCell<String> = const { RefCell::new(String::new()) };
+    // How many times "load more" has been clicked on that page.
+    static SCROLL_EXTENSIONS: Cell<u32> = const { Cell::new(0) };
+}
+
+pub fn start(
+    window: &web_sys::Window,
+    adapter: &'static dyn SiteAdapter,
+    settings: FrictionSettings,
+) {
+    if settings.disable_autoplay {
+        disable_autoplay(window);
+    }
+    if settings.pause_on_blur {
+        pause_on_blur(window, adapter);
+    }
+    if let Some(pages) = settings.scroll_page_limit.filter(|pages| *pages > 0) {
+        limit_scrolling(window, pages);
+    }
+}
+
+/// YouTube turns autoplay back on for new sessions (and the player only shows up on watch pages), so
+/// this keeps an eye on the toggle rather than flipping it once.
+fn disable_autoplay(window: &web_sys::Window) {
+    let window_clone = window.clone();
+    let closure = Closure::<dyn Fn()>::new(move || {
+        let document = window_clone
+            .document()
+            .expect("should have a document on window");
+        let toggles = selector_pack::with_active(|pack| pack.youtube.autoplay_on.join(", "));
+        if toggles.is_empty() {
+            return;
+        }
+        if let Ok(Some(toggle)) = document.query_selector(&toggles) {
+            if let Ok(toggle) = toggle.dyn_into::<web_sys::HtmlElement>() {
+                console_log!("Turning autoplay off");
+                toggle.click();
+            }
+        }
+    });
+    window
+        .set_interval_with_callback_and_timeout_and_arguments_0(
+            closure.as_ref().unchecked_ref(),
+            1000,
+        )
+        .expect("Failed to setInterval the autoplay toggle.");
+    // Same reasoning as in `record_watch_time`: lives as long as the page does.
+    closure.forget();
+}
+
+/// `blur` rather than `visibilitychange`: the latter only covers switching tabs, not switching to
+/// another window.
+fn pause_on_blur(window: &web_sys::Window, adapter: &'static dyn SiteAdapter) {
+    let window_clone = window.clone();
+    let closure = Closure::<dyn Fn()>::new(move || {
+        if let Some(document) = window_clone.document() {
+            content_script::pause_videos(&document, adapter);
+        }
+    });
+    window
+        .add_event_listener_with_callback("blur", closure.as_ref().unchecked_ref())
+        .expect("Failed to listen for the window losing focus.");
+    closure.forget();
+}
+
+/// After `pages` screenfuls, scrolling further down gets stopped (by scrolling right back up) until
+/// "load more" is clicked on the prompt that comes up. Site-agnostic on purpose: works the same for
+/// search results, comments, or whatever other feed.
+fn limit_scrolling(window: &web_sys::Window, pages: u32) {
+    let window_clone = window.clone();
+    let closure = Closure::<dyn Fn()>::new(move || {
+        let window = &window_clone;
+        let location = window.location();
+        let page = location.pathname().unwrap_or_default() + &location.search().unwrap_or_default();
+        let document = window.document().expect("should have a document on window");
+        if SCROLL_PAGE.with_borrow(|current| *current != page) {
+            SCROLL_PAGE.set(page);
+            SCROLL_EXTENSIONS.set(0);
+            if let Some(prompt) = document.get_element_by_id(LOAD_MORE_PROMPT_ID) {
+                prompt.remove();
+            }
+        }
+
+        let viewport = window
+            .inner_height()
+            .ok()
+            .and_then(|height| height.as_f64())
+            .unwrap_or_default();
+        let allowed_pages = pages * (SCROLL_EXTENSIONS.get() + 1);
+        let limit = viewport * f64::from(allowed_pages);
+        let scrolled = window.scroll_y().unwrap_or_default();
+
+        if scrolled > limit {
+            window.scroll_to_with_x_and_y(window.scroll_x().unwrap_or_default(), limit);
+            show_load_more_prompt(&document, allowed_pages);
+        }
+    });
+    window
+        .add_event_listener_with_callback("scroll", closure.as_ref().unchecked_ref())
+        .expect("Failed to listen for scrolling.");
+    closure.forget();
+}
+
+fn show_load_more_prompt(document: &web_sys::Document, pages_so_far: u32) {
+    if document.get_element_by_id(LOAD_MORE_PROMPT_ID).is_some() {
+        return;
+    }
+    let Some(body) = document.body() else {
+        return;
+    };
+
+    let prompt = document
+        .create_element("div")
+        .expect("Creating a div should never fail");
+    prompt.set_id(LOAD_MORE_PROMPT_ID);
+    // Same colours as the block overlay. The page behind it is still there, so a border keeps it
+    // from blending in when the page is the same colour.
+    let (background, color) = content_script::overlay_colors();
+    let _ = prompt.set_attribute(
+        "style",
+        &format!(
+            "position: fixed; left: 50%; bottom: 2rem; transform: translateX(-50%); \
+             z-index: 2147483647; display: flex; gap: 1rem; align-items: center; \
+             padding: 1rem 1.5rem; border-radius: 0.5rem; border: 1px solid {color}; \
+             background: {background}; color: {color}; font-size: 1.4rem;"
+        ),
+    );
+    prompt.set_inner_html(&format!(
+        "<span role=\"alert\">{}</span>\
+         <button style=\"font-size: inherit; padding: 0.25rem 0.75rem; cursor: pointer;\">{}</button>",
+        fill(t().load_more_prompt, &[("pages", &pages_so_far)]),
+        t().load_more,
+    ));
+
+    let prompt_clone = prompt.clone();
+    let on_click = Closure::<dyn Fn()>::new(move || {
+        SCROLL_EXTENSIONS.set(SCROLL_EXTENSIONS.get() + 1);
+        prompt_clone.remove();
+    });
+    if let Ok(Some(button)) = prompt.query_selector("button") {
+        let _ = button.add_event_listener_with_callback("click", on_click.as_ref().unchecked_ref());
+    }
+    // Leaks once per prompt, which takes a fair bit of scrolling to get to.
+    on_click.forget();
+
+    let _ = body.append_child(&prompt);
+}
@@ -0,0 +1,209 @@
+//! "Hard" block mode. Everything in `content_script` only edits the page, which devtools (or turning
+//! off the extension's scripts) gets around. This instead stops the requests themselves, from the
+//! background: blocking `webRequest` on Firefox and `declarativeNetRequest` on Chromium (MV3 doesn't
+//! allow blocking `webRequest`).
+//!
+//! Note that this only sees full page loads; YouTube's in-app navigation goes over its own API. The
+//! content script still does its thing on top, so those are covered the soft way.
+
+use crate::config::{BlockAction, Config};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Target {
+    Homepage,
+    Shorts,
+    Trending,
+}
+
+impl Target {
+    const ALL: [Target; 3] = [Target::Homepage, Target::Shorts, Target::Trending];
+
+    #[cfg_attr(not(feature = "firefox"), allow(dead_code))]
+    fn matches_path(&self, path: &str) -> bool {
+        match self {
+            Target::Homepage => path == "/",
+            Target::Shorts => path.starts_with("/shorts/"),
+            Target::Trending => path.starts_with("/feed/trending"),
+        }
+    }
+
+    #[cfg_attr(not(feature = "chromium"), allow(dead_code))]
+    fn regex_filter(&self) -> &'static str {
+        match self {
+            Target::Homepage => r"^https?://(www\.|m\.)?youtube\.com/?(\?.*)?(#.*)?$",
+            Target::Shorts => r"^https?://(www\.|m\.)?youtube\.com/shorts/",
+            Target::Trending => r"^https?://(www\.|m\.)?youtube\.com/feed/trending",
+        }
+    }
+
+    // declarativeNetRequest rule IDs have to be >= 1.
+    #[cfg_attr(not(feature = "chromium"), allow(dead_code))]
+    fn rule_id(&self) -> i32 {
+        *self as i32 + 1
+    }
+}
+
+/// Where a blocked request to `target` goes instead. None = it's just cancelled, which is also what
+/// happens with a redirect target that has nowhere to go (an empty/invalid one would make for an
+/// invalid declarativeNetRequest rule, taking all the other rules down with it).
+fn redirect_url(config: &Config, target: Target) -> Option<String> {
+    match (target, &config.on_block) {
+        (Target::Homepage, BlockAction::Redirect(redirect)) => redirect.url(),
+        _ => None,
+    }
+}
+
+pub use imp::*;
+
+#[cfg(feature = "firefox")]
+mod imp {
+    use super::*;
+    use crate::bindings::{self, object};
+    use std::cell::RefCell;
+    use wasm_bindgen::prelude::*;
+
+    thread_local! {
+        // The config to block with, while hard blocking is on. None = let everything through.
+        static ACTIVE: RefCell<Option<Config>> = const { RefCell::new(None) };
+    }
+
+    /// Registers the request listener. It stays registered for good, and just lets everything
+    /// through while `update()` says we're not blocking.
+    pub fn init() {
+        let on_before_request = Closure::<dyn Fn(JsValue) -> JsValue>::new(on_before_request);
+        let filter = object(&[
+            (
+                "urls",
+                js_sys::Array::of2(&"*://*.youtube.com/*".into(), &"*://youtube.com/*".into())
+                    .into(),
+            ),
+            ("types", js_sys::Array::of1(&"main_frame".into()).into()),
+        ]);
+        bindings::web_request().on_before_request().add_listener(
+            on_before_request.as_ref().unchecked_ref(),
+            &filter,
+            &js_sys::Array::of1(&"blocking".into()),
+        );
+        on_before_request.forget();
+    }
+
+    pub async fn update(config: &Config, blocked: bool) {
+        let active = (blocked && config.hard_block).then(|| config.clone());
+        ACTIVE.set(active);
+    }
+
+    // Has to answer synchronously, hence the thread_local rather than reading the storage.
+    fn on_before_request(details: JsValue) -> JsValue {
+        let url = js_sys::Reflect::get(&details, &"url".into())
+            .ok()
+            .and_then(|url| url.as_string())
+            .and_then(|url| web_sys::Url::new(&url).ok());
+        let Some(url) = url else {
+            return JsValue::UNDEFINED;
+        };
+
+        ACTIVE.with_borrow(|active| {
+            let Some(config) = active else {
+                return JsValue::UNDEFINED;
+            };
+            let path = url.pathname();
+            let Some(target) = Target::ALL.into_iter().find(|t| t.matches_path(&path)) else {
+                return JsValue::UNDEFINED;
+            };
+
+            match redirect_url(config, target) {
+                Some(redirect_url) => object(&[("redirectUrl", redirect_url.into())]).into(),
+                None => object(&[("cancel", true.into())]).into(),
+            }
+        })
+    }
+}
+
+#[cfg(feature = "chromium")]
+mod imp {
+    use super::*;
+    use crate::bindings;
+    use crate::console_log;
+    use serde::Serialize;
+    use serde_wasm_bindgen as swb;
+
+    // Shapes from https://developer.chrome.com/docs/extensions/reference/api/declarativeNetRequest
+    #[derive(Serialize)]
+    #[serde(rename_all = "camelCase")]
+    struct Rule {
+        id: i32,
+        priority: u32,
+        action: RuleAction,
+        condition: RuleCondition,
+    }
+
+    #[derive(Serialize)]
+    #[serde(tag = "type", rename_all = "camelCase")]
+    enum RuleAction {
+        Block,
+        Redirect { redirect: Redirect },
+    }
+
+    #[derive(Serialize)]
+    struct Redirect {
+        url: String,
+    }
+
+    #[derive(Serialize)]
+    #[serde(rename_all = "camelCase")]
+    struct RuleCondition {
+        regex_filter: &'static str,
+        resource_types: [&'static str; 1],
+    }
+
+    #[derive(Serialize)]
+    #[serde(rename_all = "camelCase")]
+    struct UpdateRuleOptions {
+        remove_rule_ids: Vec<i32>,
+        add_rules: Vec<Rule>,
+    }
+
+    // Rules are installed/removed as the state changes, nothing to set up in advance.
+    pub fn init() {}
+
+    /// Session rules, so that a browser restart doesn't leave stale ones behind; the background
+    /// recomputes them on startup anyways.
+    pub async fn update(config: &Config, blocked: bool) {
+        let add_rules = if blocked && config.hard_block {
+            Target::ALL
+                .into_iter()
+                .map(|target| Rule {
+                    id: target.rule_id(),
+                    priority: 1,
+                    action: match redirect_url(config, target) {
+                        Some(url) => RuleAction::Redirect {
+                            redirect: Redirect { url },
+                        },
+                        None => RuleAction::Block,
+                    },
+                    condition: RuleCondition {
+                        regex_filter: target.regex_filter(),
+                        resource_types: ["main_frame"],
+                    },
+                })
+                .collect()
+        } else {
+            vec![]
+        };
+
+        let options = UpdateRuleOptions {
+            remove_rule_ids: Target::ALL.into_iter().map(|t| t.rule_id()).collect(),
+            add_rules,
+        };
+        let options = options
+            .serialize(&swb::Serializer::json_compatible())
+            .expect("Rules are always serializable");
+
+        if let Err(e) = bindings::declarative_net_request()
+            .update_session_rules(&options.into())
+            .await
+        {
+            console_log!("Couldn't update the hard block rules: {e:?}");
+        }
+    }
+}
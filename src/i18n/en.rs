@@ -0,0 +1,271 @@
+use super::{Catalog, DayNames};
+
+pub static EN: Catalog = Catalog {
+    lang: "en",
+
+    monday: DayNames {
+        name: "Monday",
+        short: "Mon",
+        initial: "M",
+    },
+    tuesday: DayNames {
+        name: "Tuesday",
+        short: "Tue",
+        initial: "T",
+    },
+    wednesday: DayNames {
+        name: "Wednesday",
+        short: "Wed",
+        initial: "W",
+    },
+    thursday: DayNames {
+        name: "Thursday",
+        short: "Thu",
+        initial: "T",
+    },
+    friday: DayNames {
+        name: "Friday",
+        short: "Fri",
+        initial: "F",
+    },
+    saturday: DayNames {
+        name: "Saturday",
+        short: "Sat",
+        initial: "S",
+    },
+    sunday: DayNames {
+        name: "Sunday",
+        short: "Sun",
+        initial: "S",
+    },
+
+    block_message: "Go study",
+    session_limit_reached: "You've been here {minutes} minutes.",
+    session_extend: "{minutes} more minutes (once)",
+    session_end: "End the session",
+    session_over: "Session over. 🌱",
+    session_next_at: "The next one can start at {time}.",
+    leave: "Leave",
+    load_more_prompt: "That's {pages} pages of scrolling. Load more?",
+    load_more: "Load more",
+
+    notify_block_soon: "YouTube gets blocked in {minutes} minute(s).",
+    notify_budget_left: "{minutes} minute(s) of today's YouTube budget left.",
+    notify_report: "{total} on YouTube last week. Click for the full report.",
+
+    loading: "Loading...",
+    storage_not_found: "Storage bucket not found! :( It either seems like you are either using a very old browser \
+         or you're not running it in one",
+    storage_not_allowed: "You need to allow storage for this extension to work!",
+    config_corrupted: "The config is corrupted! We're gonna have to start from scratch! Try re-opening the extension popup",
+    config_corrupted_unclearable: "The config is corrupted, and clearing it didn't work either: {error}",
+    storage_error_not_allowed: "Storage isn't allowed for the extension",
+    storage_error_empty: "Nothing's been saved yet",
+    storage_error_not_found: "Couldn't find the extension's storage",
+    storage_error_corrupted: "The saved settings are corrupted",
+
+    tagline: "Control your YouTube usage with this extension.",
+    start_time: "Start Time",
+    end_time: "End Time",
+    days_of_week: "Days of the week",
+    youtube_block_days: "YouTube block days",
+    daily_budget: "Daily budget (minutes)",
+    no_limit: "No limit",
+    when_blocked: "When blocked",
+    action_blank: "Blank out the feed",
+    action_subscriptions: "Go to Subscriptions",
+    action_watch_later: "Go to Watch Later",
+    action_playlist: "Go to a playlist",
+    action_url: "Go to a custom URL",
+    action_overlay: "Cover the page",
+    playlist_id_placeholder: "Playlist ID (the list=... part)",
+    allow_filter: "Only show videos matching",
+    deny_filter: "Hide videos matching",
+    filter_rules_placeholder: "@handle, channel:Some Channel, /regex/ or a keyword. One per line.",
+    allowed_channels: "Channels still watchable when blocked",
+    allowed_channels_placeholder: "@handle or Channel Name. One per line. Leave empty to allow all.",
+    invalid_regex: "One of the /regex/ lines isn't a valid regex.",
+    same_start_end: "The start and end times can't be the same.",
+    no_days_picked: "Pick at least one day.",
+    budget_range: "Has to be between 1 and {max} minutes. Leave it empty for no limit.",
+    redirect_url: "URL to go to",
+    redirect_target_missing: "Fill this in, or pick something else to do when blocked.",
+    bad_playlist_id: "That's not a playlist ID, those only have letters, digits, - and _.",
+    redirect_not_http: "Has to be a full http:// or https:// URL.",
+    redirect_loop: "That's the YouTube homepage, which is what's getting blocked.",
+    hard_block: "Hard block (Home, Shorts and Trending don't even load)",
+    footer: "YouTube will be disabled between the specified times.",
+
+    block_site: "Block {name}",
+    site_start_time: "{name} start time",
+    site_end_time: "{name} end time",
+    time_range_to: "to",
+    site_block_days: "{name} block days",
+    site_budget_label: "{name} daily budget in minutes",
+    site_budget_placeholder: "Daily budget (minutes), no limit if empty",
+
+    preset_weekdays: "Weekdays",
+    preset_weekends: "Weekends",
+    preset_every_day: "Every day",
+
+    suggest_block_window: "Suggest a block window",
+    suggestion_length: "Window length (hours)",
+    suggestion: "{start}:00 to {end}:00 on {days} would've covered {coverage}% of your YouTube in the \
+         last {weeks} weeks.",
+    use_suggestion: "Use this (then Save)",
+    not_enough_usage: "Not enough usage to go by yet.",
+
+    session_limit: "Session limit",
+    session_minutes: "Interrupt after this many minutes in one go",
+    session_gap: "Minimum break between sessions (minutes)",
+
+    friction: "Friction (all day, not just when blocked)",
+    keep_autoplay_off: "Keep autoplay off",
+    pause_on_blur: "Pause the video when switching away",
+    scroll_limit: "Ask before scrolling past this many pages",
+    never_ask: "Never ask",
+
+    shorts: "Shorts (all day, not just when blocked)",
+    hide_shorts_shelves: "Hide Shorts shelves on Home, search and channels",
+    hide_shorts_sidebar: "Hide the Shorts sidebar entry",
+    when_opening_short: "When opening a Short",
+    short_allow: "Let it play",
+    short_watch_page: "Open it in the normal player",
+    short_block: "Block it",
+
+    dull_mode: "Dull mode",
+    dull_mode_enable: "Make YouTube dull between these times",
+    dull_mode_window: "Dull mode window {n}",
+    dull_mode_add_window: "Add another window",
+    grayscale: "Grayscale",
+    hide_view_counts: "Hide view counts",
+    tame_titles: "Tone down ALL CAPS titles",
+    thumbnails: "Thumbnails (shown again on hover)",
+    thumbnails_leave: "Leave them",
+    thumbnails_blur: "Blur them",
+    thumbnails_hide: "Hide them",
+
+    notifications: "Notifications",
+    notify_before_window: "Before the block window starts",
+    notify_before_budget: "Before the daily budget runs out",
+    lead_times: "Minutes in advance (comma separated)",
+
+    weekly_report: "Weekly report",
+    report_when: "When it's ready",
+    report_notification: "Send a notification",
+    report_open_tab: "Open it in a new tab",
+    report_off: "Do nothing",
+    report_weekday: "On",
+    open_report: "Open the report",
+
+    theme: "Theme",
+    theme_system: "Same as the system",
+    theme_light: "Light",
+    theme_dark: "Dark",
+    language: "Language",
+    language_auto: "Same as the browser",
+
+    other_sites: "Other sites",
+    custom_sites: "Custom sites",
+    invalid_pattern: "That's not a valid match pattern, try something like *://*.example.com/*",
+    site_already_added: "That site's already on the list.",
+    custom_site_save_failed: "Couldn't save the new custom site: {error}",
+    no_site_permission: "Without access to the site, there's nothing we can do on it. Re-add it to ask again.",
+    remove: "Remove",
+    remove_site: "Remove {name}",
+    css_selectors: "CSS selectors",
+    css_selectors_placeholder: "One per line, e.g #feed",
+    selector_action: "What to do with the selected elements",
+    selector_hide: "Hide them",
+    selector_replace: "Replace them with a message",
+    track_time: "Track time spent",
+    pattern_to_add: "URL match pattern of the site to add",
+    add: "Add",
+
+    youtube_selectors: "YouTube selectors",
+    pack_versions: "Bundled version {bundled}, using version {in_use}.",
+    pack_now_using: "Now using version {version}. Reload your YouTube tabs to apply it.",
+    load_pack_file: "Load a newer pack from a file",
+    couldnt_read_file: "Couldn't read {name}",
+    firefox_file_picker: "Firefox closes this popup when the file picker opens. Load it from the \
+         extension's options page instead.",
+    pack_url: "Check this URL for updates daily (optional)",
+    pack_fetch_failed: "Couldn't download the selector pack: {error}",
+    pack_invalid: "That's not a valid selector pack: {error}",
+    pack_outdated: "That pack is version {version}, but we already have version {current}",
+    pack_save_failed: "Couldn't save the selector pack: {error}",
+    enter_url_first: "Enter a URL first.",
+    check_now: "Check now",
+    self_test: "Self-test the current tab",
+    self_test_failed: "Couldn't run the self-test. Is the current tab a YouTube page?",
+    self_test_page: "{url} (pack version {version})",
+    self_test_nothing: "No selectors are meant for this page.",
+    self_test_all_matched: "All {count} selectors matched something.",
+    self_test_unmatched: "These matched nothing (also expected if the page is blocked right now):",
+
+    save: "Save",
+    saving: "Saving...",
+    saved: "Saved.",
+    save_failed: "Couldn't save: {error}",
+    set_up_first: "Set the block window up first.",
+    problem_no_days: "{what}: pick at least one day.",
+    problem_empty_window: "{what}: the start and end times can't be the same.",
+    problem_session_gap: "Session limit: the break between sessions has to be at least a minute.",
+    problem_no_lead_times: "Notifications: give at least one lead time, or turn them off.",
+    problem_lead_time: "Notifications: a lead time of {lead} minutes doesn't work, it has to be between \
+         1 and {max}.",
+    problem_budget: "{what}: a daily budget of {budget} minutes doesn't work, it has to be between 1 \
+         and {max}. Leave it empty for no budget.",
+
+    watch_time: "Watch time",
+    period_today: "Today",
+    period_this_week: "This week",
+    period_this_month: "This month",
+    total: "Total: ",
+    top_channels: "Top channels",
+    top_videos: "Top videos",
+    nothing_yet: "Nothing yet.",
+    temptations_resisted: "Temptations resisted: {count}",
+    target_home: "Home feeds",
+    target_sidebar: "Sidebars",
+    target_channel: "Channels not on the allowlist",
+    target_shorts_shelves: "Shorts shelves",
+    target_shorts_sidebar_entry: "Shorts sidebar entry",
+    target_shorts_page: "Shorts",
+    target_custom_site: "Custom sites",
+    by_hour: "By hour (last {weeks} weeks)",
+
+    streaks: "Streaks",
+    streak_under_budget: "Under the daily budget",
+    streak_no_extensions: "No session extensions",
+    streak_days: "{current} days (best {best})",
+    set_budget_first: "Set a daily budget first",
+    day_unused: "No YouTube",
+    day_good: "Good day",
+    day_bad: "Over the budget or extended a session",
+    achievements: "Achievements",
+    achievement_first_steps: ("First steps", "3 days in a row under the budget"),
+    achievement_week_one: ("Week one", "7 days in a row under the budget"),
+    achievement_touched_grass: ("Touched grass", "30 days in a row under the budget"),
+    achievement_disciplined: ("Disciplined", "7 days in a row without extending a session"),
+    achievement_iron_will: ("Iron will", "30 days in a row without extending a session"),
+
+    report_title: "TouchGrass weekly report",
+    report_load_failed: "Couldn't load the usage data: {error}",
+    previous_week: "← Previous week",
+    next_week: "Next week →",
+    this_week_so_far: "This week (so far)",
+    week_range: "{first} to {last}",
+    on_youtube: "on YouTube. {comparison}",
+    nothing_week_before: "Nothing the week before.",
+    same_as_week_before: "Same as the week before.",
+    more_than_week_before: "▲ {difference} more than the week before (+{percent}%).",
+    less_than_week_before: "▼ {difference} less than the week before (-{percent}%).",
+    times_blocked: "Times blocked",
+    session_extensions: "Session extensions",
+    by_day: "By day",
+    busiest: "Busiest: ",
+    busiest_hours: "Busiest hours",
+    no_hourly_data: "No hourly data for this week.",
+    hour_range: "{start}:00 to {end}:00",
+};
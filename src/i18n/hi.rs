@@ -0,0 +1,271 @@
+use super::{Catalog, DayNames};
+
+pub static HI: Catalog = Catalog {
+    lang: "hi",
+
+    monday: DayNames {
+        name: "सोमवार",
+        short: "सोम",
+        initial: "सो",
+    },
+    tuesday: DayNames {
+        name: "मंगलवार",
+        short: "मंगल",
+        initial: "मं",
+    },
+    wednesday: DayNames {
+        name: "बुधवार",
+        short: "बुध",
+        initial: "बु",
+    },
+    thursday: DayNames {
+        name: "गुरुवार",
+        short: "गुरु",
+        initial: "गु",
+    },
+    friday: DayNames {
+        name: "शुक्रवार",
+        short: "शुक्र",
+        initial: "शु",
+    },
+    saturday: DayNames {
+        name: "शनिवार",
+        short: "शनि",
+        initial: "श",
+    },
+    sunday: DayNames {
+        name: "रविवार",
+        short: "रवि",
+        initial: "र",
+    },
+
+    block_message: "पढ़ ले",
+    session_limit_reached: "आप {minutes} मिनट से यहाँ हैं।",
+    session_extend: "{minutes} मिनट और (एक बार)",
+    session_end: "सेशन ख़त्म करें",
+    session_over: "सेशन ख़त्म। 🌱",
+    session_next_at: "अगला सेशन {time} पर शुरू हो सकता है।",
+    leave: "बाहर निकलें",
+    load_more_prompt: "{pages} पेज स्क्रॉल हो चुके। और लोड करें?",
+    load_more: "और लोड करें",
+
+    notify_block_soon: "YouTube {minutes} मिनट में ब्लॉक हो जाएगा।",
+    notify_budget_left: "आज के YouTube बजट में {minutes} मिनट बचे हैं।",
+    notify_report: "पिछले हफ़्ते YouTube पर {total}। पूरी रिपोर्ट के लिए क्लिक करें।",
+
+    loading: "लोड हो रहा है...",
+    storage_not_found: "स्टोरेज नहीं मिला! :( लगता है या तो आप बहुत पुराना ब्राउज़र इस्तेमाल कर रहे हैं, \
+         या इसे ब्राउज़र में नहीं चला रहे",
+    storage_not_allowed: "इस एक्सटेंशन के काम करने के लिए स्टोरेज की अनुमति देनी होगी!",
+    config_corrupted: "सेटिंग्स ख़राब हो गई हैं! शुरू से सेट करना होगा! एक्सटेंशन पॉपअप दोबारा खोलकर देखें",
+    config_corrupted_unclearable: "सेटिंग्स ख़राब हो गई हैं, और उन्हें मिटाना भी काम नहीं आया: {error}",
+    storage_error_not_allowed: "एक्सटेंशन को स्टोरेज की अनुमति नहीं है",
+    storage_error_empty: "अभी तक कुछ सेव नहीं हुआ है",
+    storage_error_not_found: "एक्सटेंशन का स्टोरेज नहीं मिला",
+    storage_error_corrupted: "सेव की गई सेटिंग्स ख़राब हो गई हैं",
+
+    tagline: "इस एक्सटेंशन से अपना YouTube इस्तेमाल काबू में रखें।",
+    start_time: "शुरू होने का समय",
+    end_time: "ख़त्म होने का समय",
+    days_of_week: "हफ़्ते के दिन",
+    youtube_block_days: "YouTube ब्लॉक होने के दिन",
+    daily_budget: "रोज़ का बजट (मिनट)",
+    no_limit: "कोई सीमा नहीं",
+    when_blocked: "ब्लॉक होने पर",
+    action_blank: "फ़ीड ख़ाली कर दें",
+    action_subscriptions: "सब्सक्रिप्शन पर जाएँ",
+    action_watch_later: "बाद में देखें पर जाएँ",
+    action_playlist: "किसी प्लेलिस्ट पर जाएँ",
+    action_url: "किसी और URL पर जाएँ",
+    action_overlay: "पेज ढक दें",
+    playlist_id_placeholder: "प्लेलिस्ट ID (list=... वाला हिस्सा)",
+    allow_filter: "सिर्फ़ इनसे मिलते वीडियो दिखाएँ",
+    deny_filter: "इनसे मिलते वीडियो छिपाएँ",
+    filter_rules_placeholder: "@handle, channel:कोई चैनल, /regex/ या कोई शब्द। हर लाइन में एक।",
+    allowed_channels: "ब्लॉक होने पर भी देखे जा सकने वाले चैनल",
+    allowed_channels_placeholder: "@handle या चैनल का नाम। हर लाइन में एक। सब की छूट के लिए ख़ाली छोड़ें।",
+    invalid_regex: "/regex/ वाली कोई लाइन सही regex नहीं है।",
+    same_start_end: "शुरू और ख़त्म होने का समय एक नहीं हो सकता।",
+    no_days_picked: "कम से कम एक दिन चुनें।",
+    budget_range: "1 से {max} मिनट के बीच होना चाहिए। कोई सीमा न रखनी हो तो ख़ाली छोड़ें।",
+    redirect_url: "कहाँ जाना है, उसका URL",
+    redirect_target_missing: "इसे भरें, या ब्लॉक होने पर कुछ और चुनें।",
+    bad_playlist_id: "यह प्लेलिस्ट ID नहीं है, उसमें सिर्फ़ अक्षर, अंक, - और _ होते हैं।",
+    redirect_not_http: "पूरा http:// या https:// वाला URL होना चाहिए।",
+    redirect_loop: "यह YouTube का होमपेज है, जो ख़ुद ब्लॉक हो रहा है।",
+    hard_block: "पूरा ब्लॉक (होम, Shorts और ट्रेंडिंग लोड ही न हों)",
+    footer: "तय किए गए समय के बीच YouTube बंद रहेगा।",
+
+    block_site: "{name} ब्लॉक करें",
+    site_start_time: "{name} शुरू होने का समय",
+    site_end_time: "{name} ख़त्म होने का समय",
+    time_range_to: "से",
+    site_block_days: "{name} ब्लॉक होने के दिन",
+    site_budget_label: "{name} का रोज़ का बजट, मिनट में",
+    site_budget_placeholder: "रोज़ का बजट (मिनट), ख़ाली हो तो कोई सीमा नहीं",
+
+    preset_weekdays: "कामकाजी दिन",
+    preset_weekends: "सप्ताहांत",
+    preset_every_day: "हर दिन",
+
+    suggest_block_window: "ब्लॉक का समय सुझाएँ",
+    suggestion_length: "समय की लंबाई (घंटे)",
+    suggestion: "{days} को {start}:00 से {end}:00 तक ब्लॉक करने से पिछले {weeks} हफ़्तों का आपका \
+         {coverage}% YouTube रुक जाता।",
+    use_suggestion: "यही लगाएँ (फिर सेव करें)",
+    not_enough_usage: "सुझाव देने लायक इस्तेमाल अभी नहीं हुआ।",
+
+    session_limit: "सेशन की सीमा",
+    session_minutes: "लगातार इतने मिनट बाद रोकें",
+    session_gap: "दो सेशन के बीच कम से कम ब्रेक (मिनट)",
+
+    friction: "रुकावटें (पूरे दिन, सिर्फ़ ब्लॉक के समय नहीं)",
+    keep_autoplay_off: "ऑटोप्ले बंद रखें",
+    pause_on_blur: "कहीं और जाने पर वीडियो रोक दें",
+    scroll_limit: "इतने पेज से आगे स्क्रॉल करने से पहले पूछें",
+    never_ask: "कभी न पूछें",
+
+    shorts: "Shorts (पूरे दिन, सिर्फ़ ब्लॉक के समय नहीं)",
+    hide_shorts_shelves: "होम, सर्च और चैनलों पर Shorts की कतारें छिपाएँ",
+    hide_shorts_sidebar: "साइडबार से Shorts हटाएँ",
+    when_opening_short: "कोई Short खोलने पर",
+    short_allow: "चलने दें",
+    short_watch_page: "सामान्य प्लेयर में खोलें",
+    short_block: "ब्लॉक करें",
+
+    dull_mode: "फीका मोड",
+    dull_mode_enable: "इस समय के बीच YouTube फीका कर दें",
+    dull_mode_window: "फीका मोड का समय {n}",
+    dull_mode_add_window: "एक और समय जोड़ें",
+    grayscale: "ब्लैक एंड व्हाइट",
+    hide_view_counts: "व्यूज़ की गिनती छिपाएँ",
+    tame_titles: "कैपिटल लेटर वाले टाइटल नरम करें",
+    thumbnails: "थंबनेल (माउस ले जाने पर फिर दिखेंगे)",
+    thumbnails_leave: "जैसे हैं वैसे रहने दें",
+    thumbnails_blur: "धुंधले कर दें",
+    thumbnails_hide: "छिपा दें",
+
+    notifications: "सूचनाएँ",
+    notify_before_window: "ब्लॉक का समय शुरू होने से पहले",
+    notify_before_budget: "रोज़ का बजट ख़त्म होने से पहले",
+    lead_times: "कितने मिनट पहले (कॉमा से अलग करें)",
+
+    weekly_report: "हफ़्ते की रिपोर्ट",
+    report_when: "तैयार होने पर",
+    report_notification: "सूचना भेजें",
+    report_open_tab: "नए टैब में खोलें",
+    report_off: "कुछ न करें",
+    report_weekday: "किस दिन",
+    open_report: "रिपोर्ट खोलें",
+
+    theme: "थीम",
+    theme_system: "सिस्टम जैसी",
+    theme_light: "लाइट",
+    theme_dark: "डार्क",
+    language: "भाषा",
+    language_auto: "ब्राउज़र जैसी",
+
+    other_sites: "दूसरी साइटें",
+    custom_sites: "अपनी साइटें",
+    invalid_pattern: "यह सही मैच पैटर्न नहीं है, कुछ ऐसा आज़माएँ: *://*.example.com/*",
+    site_already_added: "यह साइट पहले से सूची में है।",
+    custom_site_save_failed: "नई साइट सेव नहीं हो पाई: {error}",
+    no_site_permission:
+        "साइट की अनुमति के बिना हम उस पर कुछ नहीं कर सकते। दोबारा पूछने के लिए उसे फिर से जोड़ें।",
+    remove: "हटाएँ",
+    remove_site: "{name} हटाएँ",
+    css_selectors: "CSS सेलेक्टर",
+    css_selectors_placeholder: "हर लाइन में एक, जैसे #feed",
+    selector_action: "चुने गए हिस्सों का क्या करें",
+    selector_hide: "छिपा दें",
+    selector_replace: "उनकी जगह एक संदेश दिखाएँ",
+    track_time: "बिताया समय गिनें",
+    pattern_to_add: "जोड़ी जाने वाली साइट का URL मैच पैटर्न",
+    add: "जोड़ें",
+
+    youtube_selectors: "YouTube सेलेक्टर",
+    pack_versions: "साथ आया वर्ज़न {bundled}, इस्तेमाल में वर्ज़न {in_use}।",
+    pack_now_using: "अब वर्ज़न {version} इस्तेमाल में है। लागू करने के लिए YouTube टैब रीलोड करें।",
+    load_pack_file: "फ़ाइल से नया पैक लोड करें",
+    couldnt_read_file: "{name} पढ़ी नहीं जा सकी",
+    firefox_file_picker: "फ़ाइल चुनते समय Firefox यह पॉपअप बंद कर देता है। इसकी जगह एक्सटेंशन के \
+         ऑप्शन पेज से लोड करें।",
+    pack_url: "इस URL पर रोज़ अपडेट देखें (ज़रूरी नहीं)",
+    pack_fetch_failed: "सेलेक्टर पैक डाउनलोड नहीं हो पाया: {error}",
+    pack_invalid: "यह सही सेलेक्टर पैक नहीं है: {error}",
+    pack_outdated: "यह पैक वर्ज़न {version} है, पर हमारे पास पहले से वर्ज़न {current} है",
+    pack_save_failed: "सेलेक्टर पैक सेव नहीं हो पाया: {error}",
+    enter_url_first: "पहले URL डालें।",
+    check_now: "अभी देखें",
+    self_test: "मौजूदा टैब की जाँच करें",
+    self_test_failed: "जाँच नहीं हो पाई। क्या मौजूदा टैब YouTube का पेज है?",
+    self_test_page: "{url} (पैक वर्ज़न {version})",
+    self_test_nothing: "इस पेज के लिए कोई सेलेक्टर नहीं है।",
+    self_test_all_matched: "सभी {count} सेलेक्टर किसी न किसी से मिले।",
+    self_test_unmatched: "ये किसी से नहीं मिले (पेज अभी ब्लॉक हो तो ऐसा होना ठीक है):",
+
+    save: "सेव करें",
+    saving: "सेव हो रहा है...",
+    saved: "सेव हो गया।",
+    save_failed: "सेव नहीं हो पाया: {error}",
+    set_up_first: "पहले ब्लॉक का समय सेट करें।",
+    problem_no_days: "{what}: कम से कम एक दिन चुनें।",
+    problem_empty_window: "{what}: शुरू और ख़त्म होने का समय एक नहीं हो सकता।",
+    problem_session_gap: "सेशन की सीमा: दो सेशन के बीच का ब्रेक कम से कम एक मिनट का होना चाहिए।",
+    problem_no_lead_times: "सूचनाएँ: कम से कम एक समय दें, या सूचनाएँ बंद कर दें।",
+    problem_lead_time: "सूचनाएँ: {lead} मिनट पहले नहीं चलेगा, यह 1 से {max} के बीच होना चाहिए।",
+    problem_budget: "{what}: {budget} मिनट का रोज़ का बजट नहीं चलेगा, यह 1 से {max} के बीच होना चाहिए। \
+         कोई बजट न रखना हो तो ख़ाली छोड़ें।",
+
+    watch_time: "देखने का समय",
+    period_today: "आज",
+    period_this_week: "इस हफ़्ते",
+    period_this_month: "इस महीने",
+    total: "कुल: ",
+    top_channels: "सबसे ज़्यादा देखे गए चैनल",
+    top_videos: "सबसे ज़्यादा देखे गए वीडियो",
+    nothing_yet: "अभी कुछ नहीं।",
+    temptations_resisted: "टाले गए लालच: {count}",
+    target_home: "होम फ़ीड",
+    target_sidebar: "साइडबार",
+    target_channel: "अनुमति सूची से बाहर के चैनल",
+    target_shorts_shelves: "Shorts की कतारें",
+    target_shorts_sidebar_entry: "साइडबार में Shorts",
+    target_shorts_page: "Shorts",
+    target_custom_site: "अपनी साइटें",
+    by_hour: "घंटे के हिसाब से (पिछले {weeks} हफ़्ते)",
+
+    streaks: "लगातार दिन",
+    streak_under_budget: "रोज़ के बजट के अंदर",
+    streak_no_extensions: "सेशन बढ़ाए बिना",
+    streak_days: "{current} दिन (सबसे ज़्यादा {best})",
+    set_budget_first: "पहले रोज़ का बजट सेट करें",
+    day_unused: "YouTube नहीं",
+    day_good: "अच्छा दिन",
+    day_bad: "बजट से ज़्यादा, या सेशन बढ़ाया",
+    achievements: "उपलब्धियाँ",
+    achievement_first_steps: ("पहले क़दम", "लगातार 3 दिन बजट के अंदर"),
+    achievement_week_one: ("पहला हफ़्ता", "लगातार 7 दिन बजट के अंदर"),
+    achievement_touched_grass: ("घास छू ली", "लगातार 30 दिन बजट के अंदर"),
+    achievement_disciplined: ("अनुशासित", "लगातार 7 दिन बिना सेशन बढ़ाए"),
+    achievement_iron_will: ("फ़ौलादी इरादा", "लगातार 30 दिन बिना सेशन बढ़ाए"),
+
+    report_title: "TouchGrass हफ़्ते की रिपोर्ट",
+    report_load_failed: "इस्तेमाल का डेटा लोड नहीं हो पाया: {error}",
+    previous_week: "← पिछला हफ़्ता",
+    next_week: "अगला हफ़्ता →",
+    this_week_so_far: "यह हफ़्ता (अब तक)",
+    week_range: "{first} से {last}",
+    on_youtube: "YouTube पर। {comparison}",
+    nothing_week_before: "उससे पिछले हफ़्ते कुछ नहीं।",
+    same_as_week_before: "उससे पिछले हफ़्ते जितना ही।",
+    more_than_week_before: "▲ उससे पिछले हफ़्ते से {difference} ज़्यादा (+{percent}%)।",
+    less_than_week_before: "▼ उससे पिछले हफ़्ते से {difference} कम (-{percent}%)।",
+    times_blocked: "कितनी बार ब्लॉक हुआ",
+    session_extensions: "बढ़ाए गए सेशन",
+    by_day: "दिन के हिसाब से",
+    busiest: "सबसे व्यस्त: ",
+    busiest_hours: "सबसे व्यस्त घंटे",
+    no_hourly_data: "इस हफ़्ते का घंटेवार डेटा नहीं है।",
+    hour_range: "{start}:00 से {end}:00",
+};
@@ -0,0 +1,346 @@
+//! Rust-side message catalog. Each language is one `Catalog`, so forgetting to translate something
+//! doesn't compile.
+//!
+//! Strings with something filled in have `{placeholders}` for `fill()`, rather than being functions;
+//! keeps the catalogs plain data that's easy to hand to a translator.
+
+mod en;
+mod hi;
+
+use crate::config::Language;
+use js_sys::Reflect;
+use std::cell::Cell;
+use wasm_bindgen::JsValue;
+
+thread_local! {
+    static ACTIVE: Cell<&'static Catalog> = const { Cell::new(&en::EN) };
+}
+
+/// Picks the language for everything after this. `Language::Auto` goes by the browser's.
+pub fn set_language(language: Language) {
+    let catalog = match language {
+        Language::English => &en::EN,
+        Language::Hindi => &hi::HI,
+        Language::Auto if browser_language().starts_with("hi") => &hi::HI,
+        Language::Auto => &en::EN,
+    };
+    ACTIVE.set(catalog);
+}
+
+fn browser_language() -> String {
+    // The global `navigator` rather than `window.navigator`: MV3's service worker doesn't have a
+    // `window`.
+    Reflect::get(&js_sys::global(), &JsValue::from_str("navigator"))
+        .and_then(|navigator| Reflect::get(&navigator, &JsValue::from_str("language")))
+        .ok()
+        .and_then(|language| language.as_string())
+        .unwrap_or_default()
+}
+
+/// Puts the picked language on the extension page's <html>, so screen readers pronounce it right.
+/// Not for content scripts: the site's own `lang` isn't ours to change.
+pub fn apply_to_page() {
+    let root = web_sys::window()
+        .and_then(|window| window.document())
+        .and_then(|document| document.document_element());
+    if let Some(root) = root {
+        let _ = root.set_attribute("lang", t().lang);
+    }
+}
+
+/// The catalog of the language picked with `set_language()`. English until then.
+pub fn t() -> &'static Catalog {
+    ACTIVE.get()
+}
+
+/// Swaps every `{name}` in `template` for its value.
+pub fn fill(template: &str, args: &[(&str, &dyn std::fmt::Display)]) -> String {
+    let mut filled = template.to_owned();
+    for (name, value) in args {
+        filled = filled.replace(&format!("{{{name}}}"), &value.to_string());
+    }
+    filled
+}
+
+pub struct Catalog {
+    /// BCP 47, for `lang` attributes.
+    pub lang: &'static str,
+
+    // Weekdays, see `Catalog::day()`.
+    pub monday: DayNames,
+    pub tuesday: DayNames,
+    pub wednesday: DayNames,
+    pub thursday: DayNames,
+    pub friday: DayNames,
+    pub saturday: DayNames,
+    pub sunday: DayNames,
+
+    // Block screen, and the other things put on top of the sites themselves.
+    /// Goes after a 🌱, which is hidden from screen readers.
+    pub block_message: &'static str,
+    pub session_limit_reached: &'static str,
+    pub session_extend: &'static str,
+    pub session_end: &'static str,
+    pub session_over: &'static str,
+    pub session_next_at: &'static str,
+    pub leave: &'static str,
+    pub load_more_prompt: &'static str,
+    pub load_more: &'static str,
+
+    // Notifications.
+    pub notify_block_soon: &'static str,
+    pub notify_budget_left: &'static str,
+    pub notify_report: &'static str,
+
+    // Popup, loading and storage trouble.
+    pub loading: &'static str,
+    pub storage_not_found: &'static str,
+    pub storage_not_allowed: &'static str,
+    pub config_corrupted: &'static str,
+    pub config_corrupted_unclearable: &'static str,
+    pub storage_error_not_allowed: &'static str,
+    pub storage_error_empty: &'static str,
+    pub storage_error_not_found: &'static str,
+    pub storage_error_corrupted: &'static str,
+
+    // Popup, the main settings.
+    pub tagline: &'static str,
+    pub start_time: &'static str,
+    pub end_time: &'static str,
+    pub days_of_week: &'static str,
+    pub youtube_block_days: &'static str,
+    pub daily_budget: &'static str,
+    pub no_limit: &'static str,
+    pub when_blocked: &'static str,
+    pub action_blank: &'static str,
+    pub action_subscriptions: &'static str,
+    pub action_watch_later: &'static str,
+    pub action_playlist: &'static str,
+    pub action_url: &'static str,
+    pub action_overlay: &'static str,
+    pub playlist_id_placeholder: &'static str,
+    pub allow_filter: &'static str,
+    pub deny_filter: &'static str,
+    pub filter_rules_placeholder: &'static str,
+    pub allowed_channels: &'static str,
+    pub allowed_channels_placeholder: &'static str,
+    pub invalid_regex: &'static str,
+    pub same_start_end: &'static str,
+    pub no_days_picked: &'static str,
+    pub budget_range: &'static str,
+    pub redirect_url: &'static str,
+    pub redirect_target_missing: &'static str,
+    pub bad_playlist_id: &'static str,
+    pub redirect_not_http: &'static str,
+    pub redirect_loop: &'static str,
+    pub hard_block: &'static str,
+    pub footer: &'static str,
+
+    // Popup, per-site schedules.
+    pub block_site: &'static str,
+    pub site_start_time: &'static str,
+    pub site_end_time: &'static str,
+    pub time_range_to: &'static str,
+    pub site_block_days: &'static str,
+    pub site_budget_label: &'static str,
+    pub site_budget_placeholder: &'static str,
+
+    // Popup, weekday toggle.
+    pub preset_weekdays: &'static str,
+    pub preset_weekends: &'static str,
+    pub preset_every_day: &'static str,
+
+    // Popup, block window suggestions.
+    pub suggest_block_window: &'static str,
+    pub suggestion_length: &'static str,
+    pub suggestion: &'static str,
+    pub use_suggestion: &'static str,
+    pub not_enough_usage: &'static str,
+
+    // Popup, session limit.
+    pub session_limit: &'static str,
+    pub session_minutes: &'static str,
+    pub session_gap: &'static str,
+
+    // Popup, friction.
+    pub friction: &'static str,
+    pub keep_autoplay_off: &'static str,
+    pub pause_on_blur: &'static str,
+    pub scroll_limit: &'static str,
+    pub never_ask: &'static str,
+
+    // Popup, Shorts.
+    pub shorts: &'static str,
+    pub hide_shorts_shelves: &'static str,
+    pub hide_shorts_sidebar: &'static str,
+    pub when_opening_short: &'static str,
+    pub short_allow: &'static str,
+    pub short_watch_page: &'static str,
+    pub short_block: &'static str,
+
+    // Popup, dull mode.
+    pub dull_mode: &'static str,
+    pub dull_mode_enable: &'static str,
+    pub dull_mode_window: &'static str,
+    pub dull_mode_add_window: &'static str,
+    pub grayscale: &'static str,
+    pub hide_view_counts: &'static str,
+    pub tame_titles: &'static str,
+    pub thumbnails: &'static str,
+    pub thumbnails_leave: &'static str,
+    pub thumbnails_blur: &'static str,
+    pub thumbnails_hide: &'static str,
+
+    // Popup, notifications.
+    pub notifications: &'static str,
+    pub notify_before_window: &'static str,
+    pub notify_before_budget: &'static str,
+    pub lead_times: &'static str,
+
+    // Popup, weekly report settings.
+    pub weekly_report: &'static str,
+    pub report_when: &'static str,
+    pub report_notification: &'static str,
+    pub report_open_tab: &'static str,
+    pub report_off: &'static str,
+    pub report_weekday: &'static str,
+    pub open_report: &'static str,
+
+    // Popup, appearance.
+    pub theme: &'static str,
+    pub theme_system: &'static str,
+    pub theme_light: &'static str,
+    pub theme_dark: &'static str,
+    pub language: &'static str,
+    pub language_auto: &'static str,
+
+    // Popup, other and custom sites.
+    pub other_sites: &'static str,
+    pub custom_sites: &'static str,
+    pub invalid_pattern: &'static str,
+    pub site_already_added: &'static str,
+    pub custom_site_save_failed: &'static str,
+    pub no_site_permission: &'static str,
+    pub remove: &'static str,
+    pub remove_site: &'static str,
+    pub css_selectors: &'static str,
+    pub css_selectors_placeholder: &'static str,
+    pub selector_action: &'static str,
+    pub selector_hide: &'static str,
+    pub selector_replace: &'static str,
+    pub track_time: &'static str,
+    pub pattern_to_add: &'static str,
+    pub add: &'static str,
+
+    // Popup, selector packs.
+    pub youtube_selectors: &'static str,
+    pub pack_versions: &'static str,
+    pub pack_now_using: &'static str,
+    pub load_pack_file: &'static str,
+    pub couldnt_read_file: &'static str,
+    pub firefox_file_picker: &'static str,
+    pub pack_url: &'static str,
+    pub pack_fetch_failed: &'static str,
+    pub pack_invalid: &'static str,
+    pub pack_outdated: &'static str,
+    pub pack_save_failed: &'static str,
+    pub enter_url_first: &'static str,
+    pub check_now: &'static str,
+    pub self_test: &'static str,
+    pub self_test_failed: &'static str,
+    pub self_test_page: &'static str,
+    pub self_test_nothing: &'static str,
+    pub self_test_all_matched: &'static str,
+    pub self_test_unmatched: &'static str,
+
+    // Popup, saving.
+    pub save: &'static str,
+    pub saving: &'static str,
+    pub saved: &'static str,
+    pub save_failed: &'static str,
+    pub set_up_first: &'static str,
+    pub problem_no_days: &'static str,
+    pub problem_empty_window: &'static str,
+    pub problem_session_gap: &'static str,
+    pub problem_no_lead_times: &'static str,
+    pub problem_lead_time: &'static str,
+    pub problem_budget: &'static str,
+
+    // Stats.
+    pub watch_time: &'static str,
+    pub period_today: &'static str,
+    pub period_this_week: &'static str,
+    pub period_this_month: &'static str,
+    pub total: &'static str,
+    pub top_channels: &'static str,
+    pub top_videos: &'static str,
+    pub nothing_yet: &'static str,
+    pub temptations_resisted: &'static str,
+    // What got blocked, see `BlockTarget::label()`.
+    pub target_home: &'static str,
+    pub target_sidebar: &'static str,
+    pub target_channel: &'static str,
+    pub target_shorts_shelves: &'static str,
+    pub target_shorts_sidebar_entry: &'static str,
+    pub target_shorts_page: &'static str,
+    pub target_custom_site: &'static str,
+    pub by_hour: &'static str,
+
+    // Streaks.
+    pub streaks: &'static str,
+    pub streak_under_budget: &'static str,
+    pub streak_no_extensions: &'static str,
+    pub streak_days: &'static str,
+    pub set_budget_first: &'static str,
+    pub day_unused: &'static str,
+    pub day_good: &'static str,
+    pub day_bad: &'static str,
+    pub achievements: &'static str,
+    // (name, description), see `Achievement::text()`.
+    pub achievement_first_steps: (&'static str, &'static str),
+    pub achievement_week_one: (&'static str, &'static str),
+    pub achievement_touched_grass: (&'static str, &'static str),
+    pub achievement_disciplined: (&'static str, &'static str),
+    pub achievement_iron_will: (&'static str, &'static str),
+
+    // Weekly report page.
+    pub report_title: &'static str,
+    pub report_load_failed: &'static str,
+    pub previous_week: &'static str,
+    pub next_week: &'static str,
+    pub this_week_so_far: &'static str,
+    pub week_range: &'static str,
+    pub on_youtube: &'static str,
+    pub nothing_week_before: &'static str,
+    pub same_as_week_before: &'static str,
+    pub more_than_week_before: &'static str,
+    pub less_than_week_before: &'static str,
+    pub times_blocked: &'static str,
+    pub session_extensions: &'static str,
+    pub by_day: &'static str,
+    pub busiest: &'static str,
+    pub busiest_hours: &'static str,
+    pub no_hourly_data: &'static str,
+    pub hour_range: &'static str,
+}
+
+pub struct DayNames {
+    pub name: &'static str,
+    pub short: &'static str,
+    pub initial: &'static str,
+}
+
+impl Catalog {
+    /// `day` counts from 0 = Monday, same as `active_days` and `WeekTime::day`.
+    pub fn day(&self, day: usize) -> &DayNames {
+        match day % 7 {
+            0 => &self.monday,
+            1 => &self.tuesday,
+            2 => &self.wednesday,
+            3 => &self.thursday,
+            4 => &self.friday,
+            5 => &self.saturday,
+            _ => &self.sunday,
+        }
+    }
+}
@@ -1,6 +1,24 @@
+mod background;
+mod bindings;
 mod config;
 mod content_script;
+mod custom_sites;
+mod dull_mode;
 mod extension_ui;
+mod feed_filter;
+mod friction;
+mod hard_block;
+mod i18n;
+mod messages;
+mod notifications;
+mod report;
+mod schedule;
+mod selector_pack;
+mod session;
+mod shorts;
+mod sites;
+mod stats;
+mod streaks;
 
 #[macro_export]
 macro_rules! console_log {
@@ -0,0 +1,43 @@
+use serde::{Deserialize, Serialize};
+use serde_wasm_bindgen as swb;
+use wasm_bindgen::JsValue;
+
+/// What the background page (or the popup) tells the content scripts over
+/// `runtime.sendMessage`/`tabs.sendMessage`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Deserialize, Serialize)]
+pub enum Message {
+    /// YouTube just got blocked/unblocked (block window boundary, budget used up, new day...).
+    BlockStateChanged { blocked: bool },
+    /// Answered with a `SelfTestReport`.
+    RunSelfTest,
+}
+
+/// Which of the current page's selectors matched nothing.
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize, Serialize)]
+pub struct SelfTestReport {
+    pub url: String,
+    pub pack_version: u32,
+    pub tested: Vec<String>,
+    pub unmatched: Vec<String>,
+}
+
+impl SelfTestReport {
+    pub fn to_js(&self) -> JsValue {
+        swb::to_value(self).expect("SelfTestReport is always serializable")
+    }
+
+    pub fn from_js(value: JsValue) -> Option<Self> {
+        swb::from_value(value).ok()
+    }
+}
+
+impl Message {
+    pub fn to_js(self) -> JsValue {
+        swb::to_value(&self).expect("Message is always serializable")
+    }
+
+    /// None if it isn't one of ours.
+    pub fn from_js(value: JsValue) -> Option<Self> {
+        swb::from_value(value).ok()
+    }
+}
@@ -0,0 +1,139 @@
+use crate::bindings::{self, object};
+use crate::config::{self, Config, Site, Usage};
+use crate::console_log;
+use crate::i18n::{fill, t};
+use crate::schedule::{self, BlockState, WeekTime};
+use serde::{Deserialize, Serialize};
+use std::cell::RefCell;
+use std::collections::HashSet;
+
+// Where `Sent` is kept. The background's memory doesn't last: the MV3 worker gets shut down
+// whenever it's idle, and then every warning would go out again.
+const SENT_KEY: &str = "notified";
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+enum Warning {
+    BlockWindow,
+    Budget,
+}
+
+/// Which (warning, lead time) pairs we've already notified about. Without this, every tick inside
+/// the lead time would fire again.
+#[derive(Default, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+struct Sent {
+    warnings: HashSet<(Warning, u32)>,
+    day: String, // `day_key()` that the budget warnings belong to
+}
+
+thread_local! {
+    // What's in storage, so it only has to be read once per background startup. None = not read yet.
+    static SENT: RefCell<Option<Sent>> = const { RefCell::new(None) };
+}
+
+async fn load_sent() {
+    if SENT.with_borrow(Option::is_some) {
+        return;
+    }
+    let sent = config::get_value(SENT_KEY).await.unwrap_or_else(|e| {
+        console_log!("Couldn't get the sent notifications, going by none: {e}");
+        None
+    });
+    SENT.with_borrow_mut(|cached| {
+        // Might've been filled in by another check while we were waiting.
+        cached.get_or_insert(sent.unwrap_or_default());
+    });
+}
+
+/// Runs `f` on `Sent`, and stores it if that changed anything.
+async fn update_sent<R>(f: impl FnOnce(&mut Sent) -> R) -> R {
+    load_sent().await;
+    let (result, changed) = SENT.with_borrow_mut(|sent| {
+        let sent = sent.get_or_insert_with(Default::default);
+        let before = sent.clone();
+        let result = f(sent);
+        let changed = (*sent != before).then(|| sent.clone());
+        (result, changed)
+    });
+    if let Some(sent) = changed {
+        if let Err(e) = config::set_value(SENT_KEY, &sent).await {
+            console_log!("Couldn't store the sent notifications: {e}");
+        }
+    }
+    result
+}
+
+/// Called periodically by the background page. Sends whichever warnings are due.
+pub async fn check(config: &Config, usage: &Usage) {
+    let settings = &config.notifications;
+    let state = schedule::block_state(config, usage, Site::YouTube);
+
+    let today = config::day_key(&js_sys::Date::new_0());
+    update_sent(|sent| {
+        if sent.day != today {
+            sent.day = today;
+            sent.warnings
+                .retain(|(warning, _)| *warning != Warning::Budget);
+        }
+        // Once the window has started, the warnings for it are spent; get ready for the next one.
+        if matches!(state, BlockState::BlockedBySchedule { .. }) {
+            sent.warnings
+                .retain(|(warning, _)| *warning != Warning::BlockWindow);
+        }
+    })
+    .await;
+
+    if state.is_blocked() {
+        return;
+    }
+
+    if settings.before_block_window {
+        let schedule = config.youtube_schedule();
+        let minutes_left = schedule::minutes_until(&schedule, WeekTime::now(), true);
+        if let Some(minutes_left) = minutes_left {
+            warn_if_due(Warning::BlockWindow, minutes_left, &settings.lead_times).await;
+        }
+    }
+
+    if settings.before_budget_runs_out {
+        if let BlockState::Allowed {
+            budget_left: Some(budget_left),
+        } = state
+        {
+            warn_if_due(Warning::Budget, budget_left, &settings.lead_times).await;
+        }
+    }
+}
+
+async fn warn_if_due(warning: Warning, minutes_left: u32, lead_times: &[u32]) {
+    // All the lead times we're already inside of count as handled, but only one notification goes
+    // out for them (e.g when the browser starts up 1 minute before the window).
+    let due: Vec<u32> = update_sent(|sent| {
+        lead_times
+            .iter()
+            .copied()
+            .filter(|&lead| minutes_left <= lead && sent.warnings.insert((warning, lead)))
+            .collect()
+    })
+    .await;
+    if due.is_empty() {
+        return;
+    }
+
+    let message = match warning {
+        Warning::BlockWindow => fill(t().notify_block_soon, &[("minutes", &minutes_left)]),
+        Warning::Budget => fill(t().notify_budget_left, &[("minutes", &minutes_left)]),
+    };
+    let options = object(&[
+        ("type", "basic".into()),
+        ("title", "TouchGrass".into()),
+        ("message", message.into()),
+        // Relative to the extension's root.
+        ("iconUrl", "icons/logo.jpeg".into()),
+    ]);
+
+    let id = format!("touch-grass-{warning:?}");
+    if let Err(e) = bindings::notifications().create(&id, &options).await {
+        console_log!("Couldn't show the {warning:?} notification: {e:?}");
+    }
+}
@@ -0,0 +1,264 @@
+//! The weekly report: a whole (Monday to Sunday) week of `Usage::daily_usage` next to the week
+//! before it. It has its own page (`extension_ui::report`), which comes up by itself once a week on
+//! `WeeklyReport::weekday`.
+
+use crate::bindings::{self, browser, object};
+use crate::config::{self, DayUsage, ReportDelivery, Storage, Usage};
+use crate::i18n::{fill, t};
+use crate::schedule::WeekTime;
+use crate::{console_log, stats};
+use std::cell::RefCell;
+use std::collections::BTreeMap;
+
+const PAGE: &str = "report.html";
+pub const NOTIFICATION_ID: &str = "touch-grass-weekly-report";
+const TOP_CHANNELS: usize = 5;
+
+thread_local! {
+    // `day_key()` of the day the report last came up. `Storage::last_report` is what really counts,
+    // this only covers refreshes that run before that has been saved.
+    static DELIVERED_ON: RefCell<String> = const { RefCell::new(String::new()) };
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct WeekReport {
+    /// `day_key()`s of the Monday and the Sunday.
+    pub first_day: String,
+    pub last_day: String,
+    /// Minutes on YouTube.
+    pub total: u32,
+    /// Same, for the week before.
+    pub previous_total: u32,
+    /// Minutes per day, Monday first.
+    pub days: [u32; 7],
+    /// Minutes per hour of the day, over the whole week.
+    pub hours: [u32; 24],
+    pub top_channels: Vec<(String, u32)>,
+    pub blocks: u32,
+    pub session_extensions: u32,
+}
+
+impl WeekReport {
+    /// (day, minutes), 0 being Monday. Busiest first, days without any usage left out.
+    pub fn busiest_days(&self, n: usize) -> Vec<(usize, u32)> {
+        busiest(&self.days, n)
+    }
+
+    /// (hour, minutes), 0 being midnight to 1am. Busiest first, hours without any usage left out.
+    pub fn busiest_hours(&self, n: usize) -> Vec<(usize, u32)> {
+        busiest(&self.hours, n)
+    }
+}
+
+fn busiest(minutes: &[u32], n: usize) -> Vec<(usize, u32)> {
+    let mut busiest: Vec<(usize, u32)> = minutes
+        .iter()
+        .copied()
+        .enumerate()
+        .filter(|(_, minutes)| *minutes > 0)
+        .collect();
+    busiest.sort_by(|(a, a_mins), (b, b_mins)| b_mins.cmp(a_mins).then_with(|| a.cmp(b)));
+    busiest.truncate(n);
+    busiest
+}
+
+/// `day_key()`s of the week `weeks_ago` weeks before this one, Monday first.
+fn week(weeks_ago: u32) -> [String; 7] {
+    let monday = config::monday_of(&js_sys::Date::new_0(), weeks_ago);
+    std::array::from_fn(|offset| {
+        let date = js_sys::Date::new_with_year_month_day(
+            monday.get_full_year(),
+            monday.get_month() as i32,
+            monday.get_date() as i32 + offset as i32,
+        );
+        config::day_key(&date)
+    })
+}
+
+/// `weeks_ago` = 0 is this week, as far as it's got.
+pub fn week_report(daily_usage: &BTreeMap<String, DayUsage>, weeks_ago: u32) -> WeekReport {
+    report_for(daily_usage, &week(weeks_ago), &week(weeks_ago + 1))
+}
+
+/// The report for the week of `keys`, compared to that of `previous_keys`.
+fn report_for(
+    daily_usage: &BTreeMap<String, DayUsage>,
+    keys: &[String; 7],
+    previous_keys: &[String; 7],
+) -> WeekReport {
+    let days: Vec<&DayUsage> = keys.iter().filter_map(|key| daily_usage.get(key)).collect();
+
+    let mut hours = [0; 24];
+    for day in &days {
+        for (total, minutes) in hours.iter_mut().zip(&day.hourly) {
+            *total += minutes;
+        }
+    }
+
+    WeekReport {
+        first_day: keys[0].clone(),
+        last_day: keys[6].clone(),
+        total: days.iter().map(|day| day.minutes).sum(),
+        previous_total: previous_keys
+            .iter()
+            .filter_map(|key| daily_usage.get(key))
+            .map(|day| day.minutes)
+            .sum(),
+        days: keys
+            .each_ref()
+            .map(|key| daily_usage.get(key).map_or(0, |day| day.minutes)),
+        hours,
+        top_channels: stats::channels_of(days.iter().copied(), TOP_CHANNELS),
+        blocks: days.iter().map(|day| day.blocks()).sum(),
+        session_extensions: days.iter().map(|day| day.session_extensions).sum(),
+    }
+}
+
+/// Called on every refresh of the background page. On the configured weekday, brings up the report
+/// for the week that just ended, once.
+pub async fn deliver_if_due(storage: &Storage, usage: &Usage) {
+    let settings = storage.user_config.weekly_report;
+    if settings.delivery == ReportDelivery::Off
+        || WeekTime::now().day != u32::from(settings.weekday)
+    {
+        return;
+    }
+    let today = config::day_key(&js_sys::Date::new_0());
+    if storage.last_report == today || DELIVERED_ON.with_borrow(|day| *day == today) {
+        return;
+    }
+
+    let report = week_report(&usage.daily_usage, 1);
+    // E.g right after installing. Not worth a notification.
+    if report.total == 0 && report.blocks == 0 {
+        return;
+    }
+
+    DELIVERED_ON.set(today.clone());
+    match settings.delivery {
+        ReportDelivery::Off => {}
+        ReportDelivery::Notification => notify(&report).await,
+        ReportDelivery::OpenTab => open().await,
+    }
+    let result = config::update_storage(|storage| storage.last_report = today).await;
+    if let Err(e) = result {
+        console_log!("Couldn't save when the weekly report came up: {e}");
+    }
+}
+
+async fn notify(report: &WeekReport) {
+    let message = fill(
+        t().notify_report,
+        &[("total", &stats::format_minutes(report.total))],
+    );
+    let options = object(&[
+        ("type", "basic".into()),
+        ("title", t().report_title.into()),
+        ("message", message.into()),
+        // Relative to the extension's root.
+        ("iconUrl", "icons/logo.jpeg".into()),
+    ]);
+    if let Err(e) = bindings::notifications()
+        .create(NOTIFICATION_ID, &options)
+        .await
+    {
+        console_log!("Couldn't show the weekly report notification: {e:?}");
+    }
+}
+
+/// Opens the report page in a new tab.
+pub async fn open() {
+    let url = bindings::extension_url(PAGE);
+    if let Err(e) = browser()
+        .tabs()
+        .create(&object(&[("url", url.into())]))
+        .await
+    {
+        console_log!("Couldn't open the weekly report: {e:?}");
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// The week of Monday `monday`, October 2026.
+    fn week_starting(monday: u32) -> [String; 7] {
+        std::array::from_fn(|offset| format!("2026-10-{:02}", monday + offset as u32))
+    }
+
+    /// The report for the week of Monday the 12th, with the one of the 5th before it.
+    fn report(days: &[(&str, DayUsage)]) -> WeekReport {
+        let daily_usage: BTreeMap<String, DayUsage> = days
+            .iter()
+            .map(|(key, day)| (key.to_string(), day.clone()))
+            .collect();
+        report_for(&daily_usage, &week_starting(12), &week_starting(5))
+    }
+
+    fn day(minutes: u32, hourly: &[(usize, u32)]) -> DayUsage {
+        let mut hours = vec![0; 24];
+        for &(hour, minutes) in hourly {
+            hours[hour] = minutes;
+        }
+        DayUsage {
+            minutes,
+            hourly: hours,
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn report_only_counts_its_own_week() {
+        // The Sunday before, the Monday and the Sunday of, and the Monday after.
+        let report = report(&[
+            ("2026-10-11", day(100, &[])),
+            ("2026-10-12", day(30, &[(9, 20), (21, 10)])),
+            ("2026-10-18", day(45, &[(21, 45)])),
+            ("2026-10-19", day(1000, &[(9, 1000)])),
+        ]);
+        assert_eq!(report.first_day, "2026-10-12");
+        assert_eq!(report.last_day, "2026-10-18");
+        assert_eq!(report.total, 75);
+        assert_eq!(report.previous_total, 100);
+        assert_eq!(report.days, [30, 0, 0, 0, 0, 0, 45]);
+        assert_eq!(report.hours[9], 20);
+        assert_eq!(report.hours[21], 55);
+        assert_eq!(report.hours.iter().sum::<u32>(), 75);
+    }
+
+    #[test]
+    fn days_without_hourly_data() {
+        let without_hours = DayUsage {
+            minutes: 60,
+            ..Default::default()
+        };
+        let report = report(&[("2026-10-13", without_hours)]);
+        assert_eq!(report.total, 60);
+        assert_eq!(report.hours, [0; 24]);
+        assert_eq!(report.busiest_hours(3), vec![]);
+    }
+
+    #[test]
+    fn busiest_days() {
+        let report = report(&[
+            ("2026-10-12", day(30, &[(20, 30)])),
+            ("2026-10-14", day(90, &[(8, 30), (20, 60)])),
+            ("2026-10-16", day(30, &[(8, 30)])),
+        ]);
+        // Ties go to the earlier one, days without any usage don't show up at all.
+        assert_eq!(report.busiest_days(7), vec![(2, 90), (0, 30), (4, 30)]);
+        assert_eq!(report.busiest_days(2), vec![(2, 90), (0, 30)]);
+    }
+
+    #[test]
+    fn busiest_hours() {
+        let report = report(&[
+            ("2026-10-12", day(30, &[(20, 30)])),
+            ("2026-10-14", day(90, &[(8, 30), (20, 60)])),
+            ("2026-10-16", day(40, &[(8, 30), (23, 10)])),
+        ]);
+        assert_eq!(report.busiest_hours(24), vec![(20, 90), (8, 60), (23, 10)]);
+        assert_eq!(report.busiest_hours(1), vec![(20, 90)]);
+    }
+}
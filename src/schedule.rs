@@ -0,0 +1,125 @@
+use crate::config::{self, Config, Schedule, Site, Usage};
+
+const MINUTES_IN_DAY: u32 = 24 * 60;
+const NUM_DAYS_IN_WEEK: u32 = 7;
+const MINUTES_IN_WEEK: u32 = NUM_DAYS_IN_WEEK * MINUTES_IN_DAY;
+
+/// Where in the (Monday-first) week a point in time is.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct WeekTime {
+    pub day: u32,    // 0 = Monday
+    pub minute: u32, // Minutes since midnight
+}
+
+impl WeekTime {
+    pub fn from_date(date: &js_sys::Date) -> Self {
+        // Sunday is zero. Fuck that, why does it start with the weekend?
+        let day = (date.get_day() + NUM_DAYS_IN_WEEK - 1) % NUM_DAYS_IN_WEEK;
+        Self {
+            day,
+            minute: date.get_hours() * 60 + date.get_minutes(),
+        }
+    }
+
+    pub fn now() -> Self {
+        Self::from_date(&js_sys::Date::new_0())
+    }
+
+    fn add_minutes(self, minutes: u32) -> Self {
+        let total = (self.day * MINUTES_IN_DAY + self.minute + minutes) % MINUTES_IN_WEEK;
+        Self {
+            day: total / MINUTES_IN_DAY,
+            minute: total % MINUTES_IN_DAY,
+        }
+    }
+}
+
+pub fn within_block_window(schedule: &Schedule, time: WeekTime) -> bool {
+    within_active_time_window(schedule, time.minute) && within_active_day_window(schedule, time.day)
+}
+
+fn within_active_time_window(schedule: &Schedule, curr_time: u32) -> bool {
+    // CAREFUL! If start_time > end_time (eg: start_time=10:00PM, end_time=6:00AM)
+    //  then it isn't a simple range-check.
+    //  Either I:
+    //  - handle both cases separately, i.e case start_time > end_time: if (end_time..24*60) || (0..start_time) and
+    //    case start_time <= end_time: another if block for normal case where start_time <= end_time.
+    //  - handle it in one go: chec if time is NOT in the range (smaller_time..larger_time).
+    //  For some reason, I like the second one better, as it can be composed without multiple if-else's,
+    //  as done below.
+    let is_normal_check = schedule.block_time_start <= schedule.block_time_end;
+    let early_hr = schedule.block_time_start.min(schedule.block_time_end);
+    let late_hr = schedule.block_time_start.max(schedule.block_time_end);
+    let outside_time_window = is_normal_check ^ (early_hr..late_hr).contains(&curr_time);
+
+    !outside_time_window
+}
+
+fn within_active_day_window(schedule: &Schedule, curr_day: u32) -> bool {
+    (schedule.active_days & (1 << curr_day)) != 0
+}
+
+/// Minutes from `from` until `within_block_window()` flips to `blocked`, or None if it never does.
+/// Brute-forcing it minute by minute is at most a week's worth (~10k) of cheap checks, and saves us
+/// from re-deriving all the wrap-around-midnight cases.
+pub fn minutes_until(schedule: &Schedule, from: WeekTime, blocked: bool) -> Option<u32> {
+    (1..=MINUTES_IN_WEEK)
+        .find(|&minutes| within_block_window(schedule, from.add_minutes(minutes)) == blocked)
+}
+
+pub fn minutes_used_today(usage: &Usage, site: Site) -> u32 {
+    let today = config::day_key(&js_sys::Date::new_0());
+    usage
+        .daily_usage
+        .get(&today)
+        .map(|day| day.minutes_on(site))
+        .unwrap_or(0)
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum BlockState {
+    /// Inside the block window. `minutes_left` is until the window ends (None = it never does).
+    BlockedBySchedule { minutes_left: Option<u32> },
+    /// Used up today's budget.
+    BudgetExhausted,
+    /// `budget_left` is None if there's no daily budget.
+    Allowed { budget_left: Option<u32> },
+}
+
+impl BlockState {
+    pub fn is_blocked(&self) -> bool {
+        !matches!(self, BlockState::Allowed { .. })
+    }
+}
+
+pub fn block_state(config: &Config, usage: &Usage, site: Site) -> BlockState {
+    match config.schedule(site) {
+        Some(schedule) => block_state_for(&schedule, minutes_used_today(usage, site)),
+        None => BlockState::Allowed { budget_left: None },
+    }
+}
+
+/// Same as `block_state()`, for when the schedule/usage don't come from one of the built-in sites.
+pub fn block_state_for(schedule: &Schedule, minutes_used_today: u32) -> BlockState {
+    let now = WeekTime::now();
+
+    if within_block_window(schedule, now) {
+        return BlockState::BlockedBySchedule {
+            minutes_left: minutes_until(schedule, now, false),
+        };
+    }
+
+    match schedule.daily_budget {
+        Some(budget) => {
+            let budget_left = budget.saturating_sub(minutes_used_today);
+            if budget_left == 0 {
+                BlockState::BudgetExhausted
+            } else {
+                BlockState::Allowed {
+                    budget_left: Some(budget_left),
+                }
+            }
+        }
+        None => BlockState::Allowed { budget_left: None },
+    }
+}
@@ -0,0 +1,153 @@
+//! YouTube renames its elements every so often, which used to mean blocking was broken until the
+//! next release. So the selectors live in a versioned data file instead (extension/selectors.json),
+//! and a newer one can be loaded from a file or a URL without waiting for one.
+
+use crate::bindings;
+use crate::config::{self, StorageError};
+use crate::i18n::{fill, t};
+use serde::{Deserialize, Serialize};
+use std::cell::RefCell;
+use std::fmt;
+use wasm_bindgen::prelude::*;
+use wasm_bindgen_futures::JsFuture;
+
+const BUNDLED: &str = include_str!("../extension/selectors.json");
+
+#[derive(Debug, Default, Clone, PartialEq, Eq, Deserialize, Serialize)]
+pub struct SelectorPack {
+    /// Higher = newer. A loaded pack only replaces the bundled one if this is higher.
+    pub version: u32,
+    pub youtube: YouTubeSelectors,
+}
+
+// Defaults so that packs from before a field was added still load. Those just don't do anything
+// for the missing bits.
+#[derive(Debug, Default, Clone, PartialEq, Eq, Deserialize, Serialize)]
+#[serde(default)]
+pub struct YouTubeSelectors {
+    pub home_feed: Vec<String>,
+    pub watch_sidebar: Vec<String>,
+    /// The feed filter's cards, across all the pages that have them.
+    pub cards: Vec<String>,
+    /// On Home, search and channel pages (the latter being the "Shorts" tab).
+    pub shorts_shelves: Vec<String>,
+    pub shorts_sidebar_entry: Vec<String>,
+    /// These three are for dull mode.
+    pub thumbnails: Vec<String>,
+    pub view_counts: Vec<String>,
+    pub titles: Vec<String>,
+    /// The player's autoplay toggle, only while it's switched on.
+    pub autoplay_on: Vec<String>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum PackError {
+    Fetch(String),
+    Invalid(String),
+    /// Not newer than what we've already got.
+    Outdated {
+        version: u32,
+        current: u32,
+    },
+    Storage(StorageError),
+}
+
+impl fmt::Display for PackError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            PackError::Fetch(e) => f.write_str(&fill(t().pack_fetch_failed, &[("error", e)])),
+            PackError::Invalid(e) => f.write_str(&fill(t().pack_invalid, &[("error", e)])),
+            PackError::Outdated { version, current } => f.write_str(&fill(
+                t().pack_outdated,
+                &[("version", version), ("current", current)],
+            )),
+            PackError::Storage(e) => f.write_str(&fill(t().pack_save_failed, &[("error", e)])),
+        }
+    }
+}
+
+impl SelectorPack {
+    pub fn parse(json: &str) -> Result<SelectorPack, PackError> {
+        let value = js_sys::JSON::parse(json)
+            .map_err(|e| PackError::Invalid(e.as_string().unwrap_or_else(|| format!("{e:?}"))))?;
+        serde_wasm_bindgen::from_value(value).map_err(|e| PackError::Invalid(e.to_string()))
+    }
+
+    pub fn bundled() -> SelectorPack {
+        SelectorPack::parse(BUNDLED).expect("the bundled selector pack should be valid")
+    }
+}
+
+thread_local! {
+    // What the adapters go by on this page. None = the bundled one (parsed lazily).
+    static ACTIVE: RefCell<Option<SelectorPack>> = const { RefCell::new(None) };
+}
+
+/// Picks whichever's newer out of the bundled pack and the one in storage.
+pub fn activate(stored: Option<&SelectorPack>) {
+    let bundled = SelectorPack::bundled();
+    let pack = match stored {
+        Some(stored) if stored.version > bundled.version => stored.clone(),
+        _ => bundled,
+    };
+    ACTIVE.set(Some(pack));
+}
+
+pub fn with_active<R>(f: impl FnOnce(&SelectorPack) -> R) -> R {
+    ACTIVE.with_borrow_mut(|active| f(active.get_or_insert_with(SelectorPack::bundled)))
+}
+
+/// Saves `json` as the pack to use from now on, if it's valid and newer than the current one.
+pub async fn install(json: &str) -> Result<SelectorPack, PackError> {
+    let pack = SelectorPack::parse(json)?;
+
+    let stored = config::get_storage()
+        .await
+        .ok()
+        .and_then(|storage| storage.selector_pack);
+    let current = stored
+        .map(|stored| stored.version)
+        .unwrap_or_default()
+        .max(SelectorPack::bundled().version);
+    if pack.version <= current {
+        return Err(PackError::Outdated {
+            version: pack.version,
+            current,
+        });
+    }
+
+    let new_pack = pack.clone();
+    config::update_storage(move |storage| storage.selector_pack = Some(new_pack))
+        .await
+        .map_err(PackError::Storage)?;
+    Ok(pack)
+}
+
+pub async fn install_from_url(url: &str) -> Result<SelectorPack, PackError> {
+    let fetch_error =
+        |e: JsValue| PackError::Fetch(e.as_string().unwrap_or_else(|| format!("{e:?}")));
+
+    let response: web_sys::Response = bindings::fetch(url)
+        .await
+        .map_err(fetch_error)?
+        .unchecked_into();
+    if !response.ok() {
+        return Err(PackError::Fetch(format!("HTTP {}", response.status())));
+    }
+    let text = JsFuture::from(response.text().map_err(fetch_error)?)
+        .await
+        .map_err(fetch_error)?
+        .as_string()
+        .unwrap_or_default();
+    install(&text).await
+}
+
+/// The ones out of `selectors` that don't match anything on the page right now. If YouTube renamed
+/// something, it shows up here.
+pub fn self_test(document: &web_sys::Document, selectors: &[String]) -> Vec<String> {
+    selectors
+        .iter()
+        .filter(|selector| !matches!(document.query_selector(selector), Ok(Some(_))))
+        .cloned()
+        .collect()
+}
@@ -0,0 +1,408 @@
+//! Per-session limit: the daily budget doesn't stop one long binge, so after `SessionLimit::minutes`
+//! of continuous YouTube you get interrupted and have to pick between leaving, a one-time extension,
+//! or ending the session. An ended session means a break of at least `SessionLimit::min_gap`.
+//!
+//! "Continuous" = without a break of `min_gap` or longer. So leaving for a bit and coming right back
+//! doesn't start a fresh session either.
+
+use crate::config::{self, SessionLimit};
+use crate::i18n::{fill, t};
+use crate::sites::SiteAdapter;
+use crate::{console_log, content_script};
+use serde::{Deserialize, Serialize};
+use wasm_bindgen::prelude::*;
+use wasm_bindgen_futures::spawn_local;
+
+const EXTENSION_MINUTES: u32 = 5;
+const OVERLAY_ID: &str = "touch-grass-session-overlay";
+const MINUTE_MS: u64 = 60 * 1000;
+
+/// Lives in `config::Usage`, so that all YouTube tabs count towards (and get interrupted by) the same
+/// session.
+#[derive(Debug, Default, Clone, PartialEq, Eq, Deserialize, Serialize)]
+#[serde(default)]
+pub struct Session {
+    pub minutes: u32,
+    pub extended: bool,
+    /// `Date.now()` of the last minute counted.
+    pub last_tick: u64,
+    /// Set when the session was ended by hand, which starts the break.
+    pub ended_at: Option<u64>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum SessionStatus {
+    Ok,
+    LimitReached { minutes: u32, can_extend: bool },
+    OnBreak { until: u64 },
+}
+
+/// In ms. A gap of 0 would start a new session on every tick, so it's at least a minute even if
+/// the config slipped one through.
+fn gap(limit: &SessionLimit) -> u64 {
+    u64::from(limit.min_gap.max(1)) * MINUTE_MS
+}
+
+impl Session {
+    /// Called from the content script's tracking timer, once a minute per YouTube tab.
+    pub fn tick(&mut self, limit: &SessionLimit, now: u64) {
+        let gap = gap(limit);
+        match self.ended_at {
+            Some(ended_at) if now.saturating_sub(ended_at) < gap => return,
+            Some(_) => *self = Session::default(),
+            None if self.away_long_enough(limit, now) => *self = Session::default(),
+            None => {}
+        }
+        // The interrupt is up, so nothing's being watched. Sitting on it long enough counts as the
+        // break, same as being away would.
+        if matches!(self.status(limit, now), SessionStatus::LimitReached { .. }) {
+            return;
+        }
+        // With several tabs open, each one ticks. Only the first one in a minute counts.
+        if now.saturating_sub(self.last_tick) < MINUTE_MS * 9 / 10 {
+            return;
+        }
+        self.minutes += 1;
+        self.last_tick = now;
+    }
+
+    /// Whether there's been a break of `min_gap` since the last tick. The ticks are a minute apart
+    /// to begin with, so that minute doesn't count towards it.
+    fn away_long_enough(&self, limit: &SessionLimit, now: u64) -> bool {
+        now.saturating_sub(self.last_tick) >= gap(limit) + MINUTE_MS
+    }
+
+    pub fn status(&self, limit: &SessionLimit, now: u64) -> SessionStatus {
+        let Some(max_minutes) = limit.minutes else {
+            return SessionStatus::Ok;
+        };
+        if let Some(ended_at) = self.ended_at {
+            let until = ended_at + gap(limit);
+            if now < until {
+                return SessionStatus::OnBreak { until };
+            }
+            return SessionStatus::Ok;
+        }
+        // Gone for long enough that the next tick starts a new session.
+        if self.away_long_enough(limit, now) {
+            return SessionStatus::Ok;
+        }
+
+        let allowed = max_minutes + if self.extended { EXTENSION_MINUTES } else { 0 };
+        if self.minutes >= allowed {
+            SessionStatus::LimitReached {
+                minutes: self.minutes,
+                can_extend: !self.extended,
+            }
+        } else {
+            SessionStatus::Ok
+        }
+    }
+}
+
+pub fn now() -> u64 {
+    js_sys::Date::now() as u64
+}
+
+/// Shows (or takes down) the interrupt according to where the session's at.
+pub fn enforce(
+    window: &web_sys::Window,
+    adapter: &'static dyn SiteAdapter,
+    limit: SessionLimit,
+    session: &Session,
+) {
+    let document = window.document().expect("should have a document on window");
+    let status = session.status(&limit, now());
+
+    let html = match status {
+        SessionStatus::Ok => {
+            content_script::remove_block_overlay(&document, OVERLAY_ID);
+            return;
+        }
+        SessionStatus::LimitReached {
+            minutes,
+            can_extend,
+        } => {
+            let extend = if can_extend {
+                format!(
+                    "<button data-choice=\"extend\">{}</button>",
+                    fill(t().session_extend, &[("minutes", &EXTENSION_MINUTES)])
+                )
+            } else {
+                String::new()
+            };
+            format!(
+                "<h1>{}</h1>\
+                 <div style=\"display: flex; gap: 1rem;\">\
+                 <button data-choice=\"leave\">{}</button>{extend}\
+                 <button data-choice=\"end\">{}</button></div>",
+                fill(t().session_limit_reached, &[("minutes", &minutes)]),
+                t().leave,
+                t().session_end,
+            )
+        }
+        SessionStatus::OnBreak { until } => {
+            let until = js_sys::Date::new(&(until as f64).into());
+            let time = format!("{:02}:{:02}", until.get_hours(), until.get_minutes());
+            format!(
+                "<h1>{}</h1><p>{}</p><div><button data-choice=\"leave\">{}</button></div>",
+                t().session_over,
+                fill(t().session_next_at, &[("time", &time)]),
+                t().leave,
+            )
+        }
+    };
+
+    // Re-rendered from scratch whenever it changes, e.g from the limit to the break.
+    if let Some(overlay) = document.get_element_by_id(OVERLAY_ID) {
+        if overlay.get_attribute("data-status").as_deref() == Some(&html) {
+            return;
+        }
+        content_script::remove_block_overlay(&document, OVERLAY_ID);
+    }
+    content_script::show_block_overlay(&document, OVERLAY_ID);
+    let Some(overlay) = document.get_element_by_id(OVERLAY_ID) else {
+        return;
+    };
+    let _ = overlay.set_attribute("data-status", &html);
+    let _ = overlay.set_attribute(
+        "style",
+        &(overlay.get_attribute("style").unwrap_or_default()
+            + " flex-direction: column; gap: 2rem; font-size: 1.6rem;"),
+    );
+    overlay.set_inner_html(&html);
+    // Again, now that there's a new heading and buttons to label it and take the focus.
+    content_script::make_modal(&document, &overlay);
+    content_script::pause_videos(&document, adapter);
+
+    let Ok(buttons) = overlay.query_selector_all("button") else {
+        return;
+    };
+    for i in 0..buttons.length() {
+        let Some(button) = buttons
+            .item(i)
+            .and_then(|node| node.dyn_into::<web_sys::Element>().ok())
+        else {
+            continue;
+        };
+        let choice = button.get_attribute("data-choice").unwrap_or_default();
+        let window = window.clone();
+        let on_click = Closure::<dyn Fn()>::new(move || {
+            on_choice(window.clone(), adapter, limit, choice.clone())
+        });
+        let _ = button.add_event_listener_with_callback("click", on_click.as_ref().unchecked_ref());
+        // Leaks a handful of closures per interrupt, which happens maybe a few times a day.
+        on_click.forget();
+    }
+}
+
+fn on_choice(
+    window: web_sys::Window,
+    adapter: &'static dyn SiteAdapter,
+    limit: SessionLimit,
+    choice: String,
+) {
+    if choice == "leave" {
+        // Nothing to update: staying away for the break is what ends the session.
+        let _ = window.location().replace("about:blank");
+        return;
+    }
+
+    spawn_local(async move {
+        let result = config::update_usage(|usage| match choice.as_str() {
+            "extend" => {
+                usage.session.extended = true;
+                let today = config::day_key(&js_sys::Date::new_0());
+                usage
+                    .daily_usage
+                    .entry(today)
+                    .or_default()
+                    .session_extensions += 1;
+            }
+            "end" => usage.session.ended_at = Some(now()),
+            _ => {}
+        })
+        .await;
+        match result {
+            Ok(usage) => enforce(&window, adapter, limit, &usage.session),
+            Err(e) => {
+                console_log!("Couldn't update the session: {e}");
+            }
+        }
+    });
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const START: u64 = 1_000 * MINUTE_MS;
+
+    fn limit(minutes: u32, min_gap: u32) -> SessionLimit {
+        SessionLimit {
+            minutes: Some(minutes),
+            min_gap,
+        }
+    }
+
+    /// A tick every minute for `minutes` minutes, from `from`. Returns when the last one was.
+    fn watch(session: &mut Session, limit: &SessionLimit, from: u64, minutes: u64) -> u64 {
+        for minute in 0..minutes {
+            session.tick(limit, from + minute * MINUTE_MS);
+        }
+        from + minutes.saturating_sub(1) * MINUTE_MS
+    }
+
+    #[test]
+    fn ticks_count_once_a_minute() {
+        let limit = limit(30, 15);
+        let mut session = Session::default();
+        let last = watch(&mut session, &limit, START, 10);
+        assert_eq!(session.minutes, 10);
+
+        // Another tab ticking in the same minute.
+        session.tick(&limit, last + 1000);
+        assert_eq!(session.minutes, 10);
+    }
+
+    #[test]
+    fn short_break_continues_the_session() {
+        let limit = limit(30, 15);
+        let mut session = Session::default();
+        let last = watch(&mut session, &limit, START, 10);
+
+        // Last counted at the start of its minute, so this is only 14 minutes away.
+        session.tick(&limit, last + 15 * MINUTE_MS);
+        assert_eq!(session.minutes, 11);
+    }
+
+    #[test]
+    fn break_of_min_gap_starts_a_new_session() {
+        let limit = limit(30, 15);
+        let mut session = Session::default();
+        let last = watch(&mut session, &limit, START, 10);
+
+        assert_eq!(
+            session.status(&limit, last + 16 * MINUTE_MS),
+            SessionStatus::Ok
+        );
+        session.tick(&limit, last + 16 * MINUTE_MS);
+        assert_eq!(session.minutes, 1);
+    }
+
+    #[test]
+    fn limit_and_extension() {
+        let limit = limit(30, 15);
+        let mut session = Session::default();
+        let last = watch(&mut session, &limit, START, 29);
+        assert_eq!(session.status(&limit, last), SessionStatus::Ok);
+
+        let last = watch(&mut session, &limit, last + MINUTE_MS, 1);
+        assert_eq!(
+            session.status(&limit, last),
+            SessionStatus::LimitReached {
+                minutes: 30,
+                can_extend: true
+            }
+        );
+
+        session.extended = true;
+        assert_eq!(session.status(&limit, last), SessionStatus::Ok);
+        let last = watch(
+            &mut session,
+            &limit,
+            last + MINUTE_MS,
+            EXTENSION_MINUTES.into(),
+        );
+        assert_eq!(
+            session.status(&limit, last),
+            SessionStatus::LimitReached {
+                minutes: 30 + EXTENSION_MINUTES,
+                can_extend: false
+            }
+        );
+    }
+
+    #[test]
+    fn interrupted_session_stops_counting() {
+        let limit = limit(30, 15);
+        let mut session = Session::default();
+        let last = watch(&mut session, &limit, START, 40);
+        assert_eq!(session.minutes, 30);
+        assert_eq!(
+            session.status(&limit, last),
+            SessionStatus::LimitReached {
+                minutes: 30,
+                can_extend: true
+            }
+        );
+
+        // Left on the interrupt for the whole gap.
+        session.tick(&limit, last + 7 * MINUTE_MS);
+        assert_eq!(session.minutes, 1);
+    }
+
+    #[test]
+    fn ended_session_is_a_break() {
+        let limit = limit(30, 15);
+        let mut session = Session::default();
+        let last = watch(&mut session, &limit, START, 30);
+        session.ended_at = Some(last);
+
+        let until = last + 15 * MINUTE_MS;
+        assert_eq!(
+            session.status(&limit, last + MINUTE_MS),
+            SessionStatus::OnBreak { until }
+        );
+        // Watching during the break doesn't count towards anything.
+        session.tick(&limit, last + MINUTE_MS);
+        assert_eq!(session.minutes, 30);
+
+        assert_eq!(session.status(&limit, until), SessionStatus::Ok);
+        session.tick(&limit, until);
+        assert_eq!(session.minutes, 1);
+        assert_eq!(session.ended_at, None);
+    }
+
+    #[test]
+    fn no_limit_is_always_ok() {
+        let limit = SessionLimit {
+            minutes: None,
+            min_gap: 15,
+        };
+        let mut session = Session::default();
+        let last = watch(&mut session, &limit, START, 600);
+        assert_eq!(session.status(&limit, last), SessionStatus::Ok);
+    }
+
+    #[test]
+    fn late_ticks_are_no_break() {
+        let limit = limit(30, 1);
+        let mut session = Session::default();
+        for minute in 0..10 {
+            // setInterval isn't exact, especially in a background tab.
+            session.tick(&limit, START + minute * (MINUTE_MS + 5000));
+        }
+        assert_eq!(session.minutes, 10);
+    }
+
+    #[test]
+    fn zero_gap_counts_as_a_minute() {
+        let limit = limit(30, 0);
+        let mut session = Session::default();
+        let last = watch(&mut session, &limit, START, 30);
+        assert_eq!(session.minutes, 30);
+        assert!(matches!(
+            session.status(&limit, last),
+            SessionStatus::LimitReached { .. }
+        ));
+
+        session.ended_at = Some(last);
+        assert_eq!(
+            session.status(&limit, last),
+            SessionStatus::OnBreak {
+                until: last + MINUTE_MS
+            }
+        );
+    }
+}
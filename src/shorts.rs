@@ -0,0 +1,78 @@
+//! Shorts mode. Separate from the rest of the blocking since it doesn't care about the block window:
+//! whatever's turned on in `ShortsSettings` applies all day.
+
+use crate::config::{BlockTarget, SelectorAction, ShortsPageAction, ShortsSettings};
+use crate::sites::SiteAdapter;
+use crate::{console_log, content_script, selector_pack};
+use wasm_bindgen::prelude::*;
+
+const SHORTS_OVERLAY_ID: &str = "touch-grass-shorts-overlay";
+
+/// Polls every second, same as the feed filter. YouTube loads the shelves in late and gets to
+/// `/shorts/...` by in-app navigation, so there's nothing to hook into once at page load.
+pub fn start(
+    window: &web_sys::Window,
+    adapter: &'static dyn SiteAdapter,
+    settings: ShortsSettings,
+) {
+    if settings == ShortsSettings::default() {
+        return;
+    }
+
+    let window_clone = window.clone();
+    let closure = Closure::<dyn Fn()>::new(move || enforce(&window_clone, adapter, settings));
+    window
+        .set_interval_with_callback_and_timeout_and_arguments_0(
+            closure.as_ref().unchecked_ref(),
+            1000,
+        )
+        .expect("Failed to setInterval the Shorts blocker.");
+    // Same reasoning as in `record_watch_time`: lives as long as the page does.
+    closure.forget();
+}
+
+fn enforce(window: &web_sys::Window, adapter: &dyn SiteAdapter, settings: ShortsSettings) {
+    let document = window.document().expect("should have a document on window");
+
+    let (shelves, sidebar_entry) = selector_pack::with_active(|pack| {
+        (
+            pack.youtube.shorts_shelves.clone(),
+            pack.youtube.shorts_sidebar_entry.clone(),
+        )
+    });
+    for (enabled, selectors, target) in [
+        (settings.hide_shelves, shelves, BlockTarget::ShortsShelves),
+        (
+            settings.hide_sidebar_entry,
+            sidebar_entry,
+            BlockTarget::ShortsSidebarEntry,
+        ),
+    ] {
+        if enabled
+            && content_script::remove_distractions(&document, &selectors, SelectorAction::Hide) > 0
+        {
+            content_script::count_block(target);
+        }
+    }
+
+    let path = window.location().pathname().unwrap_or_default();
+    let Some(short_id) = path.strip_prefix("/shorts/").filter(|id| !id.is_empty()) else {
+        content_script::remove_block_overlay(&document, SHORTS_OVERLAY_ID);
+        return;
+    };
+
+    match settings.on_shorts_page {
+        ShortsPageAction::Allow => {}
+        ShortsPageAction::WatchPage => {
+            let url = format!("/watch?v={}", short_id.trim_end_matches('/'));
+            console_log!("Sending Short over to {url}");
+            // replace() so that going back doesn't land on the Short again (and bounce right back).
+            let _ = window.location().replace(&url);
+        }
+        ShortsPageAction::Block => {
+            content_script::show_block_overlay(&document, SHORTS_OVERLAY_ID);
+            content_script::pause_videos(&document, adapter);
+            content_script::count_block(BlockTarget::ShortsPage);
+        }
+    }
+}
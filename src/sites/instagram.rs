@@ -0,0 +1,50 @@
+use super::{is_host_of, owned, path_segment, text_of, MediaInfo, SiteAdapter};
+use crate::config::Site;
+
+pub struct Instagram;
+
+impl SiteAdapter for Instagram {
+    fn site(&self) -> Site {
+        Site::Instagram
+    }
+
+    fn matches_host(&self, host: &str) -> bool {
+        is_host_of(host, "instagram.com")
+    }
+
+    fn is_homepage(&self, path: &str) -> bool {
+        path == "/"
+    }
+
+    fn distraction_selectors(&self, path: &str) -> Vec<String> {
+        // Instagram's markup is mostly anonymous <div>s, so there's nothing more specific than "the
+        // main content" to go on. Only on the pages that are pure feed though, otherwise a single
+        // post would get blanked out too.
+        if self.is_homepage(path) || path.starts_with("/explore") || path.starts_with("/reels") {
+            owned(&["main[role='main']"])
+        } else {
+            Vec::new()
+        }
+    }
+
+    fn current_media(&self, document: &web_sys::Document) -> Option<MediaInfo> {
+        let path = document.location()?.pathname().ok()?;
+        // /p/<id> for posts, /reel/<id> for reels
+        if !matches!(path_segment(&path, 0)?, "p" | "reel") {
+            return None;
+        }
+
+        let handle = document
+            .query_selector("article header a[href^='/']")
+            .ok()
+            .flatten()
+            .and_then(|el| el.get_attribute("href"))
+            .map(|href| href.trim_matches('/').to_owned());
+        Some(MediaInfo {
+            id: path_segment(&path, 1).map(str::to_owned),
+            title: text_of(document.query_selector("article h1")).unwrap_or_default(),
+            channel_name: handle.clone(),
+            channel_handle: handle,
+        })
+    }
+}
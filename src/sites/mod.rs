@@ -0,0 +1,96 @@
+//! Everything that's specific to one site (selectors, URL layout, where the metadata lives) sits
+//! behind `SiteAdapter`, so that `content_script` can stay the same for all of them.
+
+mod instagram;
+mod reddit;
+mod twitch;
+mod twitter;
+mod youtube;
+
+use crate::config::Site;
+use wasm_bindgen::JsValue;
+
+/// Whatever's being watched/read, as far as tracking and filtering are concerned. What counts as a
+/// "channel" depends on the site: a YouTube channel, a subreddit, an X account...
+#[derive(Debug, Default, Clone, PartialEq, Eq)]
+pub struct MediaInfo {
+    pub id: Option<String>,
+    pub title: String,
+    pub channel_name: Option<String>,
+    pub channel_handle: Option<String>,
+}
+
+pub trait SiteAdapter {
+    fn site(&self) -> Site;
+
+    fn matches_host(&self, host: &str) -> bool;
+
+    fn is_homepage(&self, path: &str) -> bool;
+
+    /// CSS selectors for the distracting parts of the page at `path`. These get blanked out when
+    /// blocked. Owned, since YouTube's come from the selector pack.
+    fn distraction_selectors(&self, path: &str) -> Vec<String>;
+
+    /// The single recommendation "cards" that the feed filter looks at. None if there's no feed
+    /// filtering for this site.
+    fn card_selector(&self) -> Option<String> {
+        None
+    }
+
+    fn card_info(&self, _card: &web_sys::Element) -> Option<MediaInfo> {
+        None
+    }
+
+    /// What's being watched on the current page, if it's a "watch page" at all (a video, a post, a
+    /// stream...).
+    fn current_media(&self, document: &web_sys::Document) -> Option<MediaInfo>;
+
+    /// CSS selector for the element(s) that play the video.
+    fn playback_selector(&self) -> &'static str {
+        "video"
+    }
+}
+
+static ADAPTERS: [&(dyn SiteAdapter + Sync); 5] = [
+    &youtube::YouTube,
+    &reddit::Reddit,
+    &twitter::Twitter,
+    &instagram::Instagram,
+    &twitch::Twitch,
+];
+
+pub fn adapter_for_host(host: &str) -> Option<&'static dyn SiteAdapter> {
+    ADAPTERS
+        .into_iter()
+        .find(|adapter| adapter.matches_host(host))
+        .map(|adapter| adapter as &'static dyn SiteAdapter)
+}
+
+fn owned(selectors: &[&str]) -> Vec<String> {
+    selectors
+        .iter()
+        .map(|selector| selector.to_string())
+        .collect()
+}
+
+/// Whether `host` is `domain` or one of its subdomains.
+fn is_host_of(host: &str, domain: &str) -> bool {
+    host == domain
+        || host
+            .strip_suffix(domain)
+            .is_some_and(|prefix| prefix.ends_with('.'))
+}
+
+/// Trimmed text content of whatever `query_selector` found, if it's non-empty.
+fn text_of(el: Result<Option<web_sys::Element>, JsValue>) -> Option<String> {
+    el.ok()
+        .flatten()
+        .and_then(|el| el.text_content())
+        .map(|text| text.trim().to_owned())
+        .filter(|text| !text.is_empty())
+}
+
+/// The `n`th segment of `path`, i.e `/a/b/c` -> 0: a, 1: b, 2: c.
+fn path_segment(path: &str, n: usize) -> Option<&str> {
+    path.split('/').filter(|segment| !segment.is_empty()).nth(n)
+}
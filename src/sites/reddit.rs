@@ -0,0 +1,44 @@
+use super::{is_host_of, owned, path_segment, text_of, MediaInfo, SiteAdapter};
+use crate::config::Site;
+
+pub struct Reddit;
+
+impl SiteAdapter for Reddit {
+    fn site(&self) -> Site {
+        Site::Reddit
+    }
+
+    fn matches_host(&self, host: &str) -> bool {
+        is_host_of(host, "reddit.com")
+    }
+
+    fn is_homepage(&self, path: &str) -> bool {
+        matches!(
+            path,
+            "/" | "/r/popular/" | "/r/popular" | "/r/all/" | "/r/all"
+        )
+    }
+
+    fn distraction_selectors(&self, _path: &str) -> Vec<String> {
+        // New Reddit's feed, old Reddit's feed, and the "recent/popular" sidebar.
+        owned(&["shreddit-feed", "#siteTable", "#right-sidebar-container"])
+    }
+
+    // The subreddit is the "channel".
+    fn current_media(&self, document: &web_sys::Document) -> Option<MediaInfo> {
+        let path = document.location()?.pathname().ok()?;
+        // /r/<subreddit>/comments/<id>/<slug>
+        if path_segment(&path, 0)? != "r" || path_segment(&path, 2)? != "comments" {
+            return None;
+        }
+        let subreddit = path_segment(&path, 1)?;
+
+        Some(MediaInfo {
+            id: path_segment(&path, 3).map(str::to_owned),
+            title: text_of(document.query_selector("shreddit-post h1, [slot='title']"))
+                .unwrap_or_default(),
+            channel_name: Some(format!("r/{subreddit}")),
+            channel_handle: Some(subreddit.to_owned()),
+        })
+    }
+}
@@ -0,0 +1,63 @@
+use super::{is_host_of, owned, path_segment, text_of, MediaInfo, SiteAdapter};
+use crate::config::Site;
+
+pub struct Twitch;
+
+// Top-level paths that aren't channels.
+const NON_CHANNEL_PAGES: [&str; 7] = [
+    "directory",
+    "search",
+    "settings",
+    "subscriptions",
+    "inventory",
+    "wallet",
+    "downloads",
+];
+
+impl SiteAdapter for Twitch {
+    fn site(&self) -> Site {
+        Site::Twitch
+    }
+
+    fn matches_host(&self, host: &str) -> bool {
+        is_host_of(host, "twitch.tv")
+    }
+
+    fn is_homepage(&self, path: &str) -> bool {
+        path == "/" || path.starts_with("/directory")
+    }
+
+    fn distraction_selectors(&self, _path: &str) -> Vec<String> {
+        // The autoplaying front page carousel, the recommendations below it, and the sidebar full
+        // of live channels.
+        owned(&[
+            "[data-a-target='front-page-carousel']",
+            "[data-a-target='front-page-content']",
+            "[data-a-target='side-nav-bar']",
+        ])
+    }
+
+    // Channel pages are the "watch pages": /<channel>, or /videos/<id> for VODs.
+    fn current_media(&self, document: &web_sys::Document) -> Option<MediaInfo> {
+        let path = document.location()?.pathname().ok()?;
+        let first = path_segment(&path, 0)?;
+        if NON_CHANNEL_PAGES.contains(&first) {
+            return None;
+        }
+
+        let channel_name = text_of(document.query_selector(".channel-info-content h1"));
+        let (id, channel_handle) = if first == "videos" {
+            (path_segment(&path, 1).map(str::to_owned), None)
+        } else {
+            (None, Some(first.to_owned()))
+        };
+
+        Some(MediaInfo {
+            id,
+            title: text_of(document.query_selector("[data-a-target='stream-title']"))
+                .unwrap_or_default(),
+            channel_name: channel_name.or_else(|| channel_handle.clone()),
+            channel_handle,
+        })
+    }
+}
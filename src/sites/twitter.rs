@@ -0,0 +1,45 @@
+use super::{is_host_of, owned, path_segment, text_of, MediaInfo, SiteAdapter};
+use crate::config::Site;
+
+pub struct Twitter;
+
+impl SiteAdapter for Twitter {
+    fn site(&self) -> Site {
+        Site::Twitter
+    }
+
+    fn matches_host(&self, host: &str) -> bool {
+        is_host_of(host, "x.com") || is_host_of(host, "twitter.com")
+    }
+
+    fn is_homepage(&self, path: &str) -> bool {
+        matches!(path, "/" | "/home" | "/explore")
+    }
+
+    fn distraction_selectors(&self, _path: &str) -> Vec<String> {
+        // The home timeline, and the "What's happening"/"Who to follow" column.
+        owned(&[
+            "[aria-label='Timeline: Your Home Timeline']",
+            "[aria-label='Timeline: Explore']",
+            "[data-testid='sidebarColumn']",
+        ])
+    }
+
+    // The account that posted is the "channel".
+    fn current_media(&self, document: &web_sys::Document) -> Option<MediaInfo> {
+        let path = document.location()?.pathname().ok()?;
+        // /<handle>/status/<id>
+        if path_segment(&path, 1)? != "status" {
+            return None;
+        }
+        let handle = path_segment(&path, 0)?;
+
+        Some(MediaInfo {
+            id: path_segment(&path, 2).map(str::to_owned),
+            title: text_of(document.query_selector("article [data-testid='tweetText']"))
+                .unwrap_or_default(),
+            channel_name: text_of(document.query_selector("article [data-testid='User-Name']")),
+            channel_handle: Some(handle.to_owned()),
+        })
+    }
+}
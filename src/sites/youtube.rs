@@ -0,0 +1,76 @@
+use super::{is_host_of, text_of, MediaInfo, SiteAdapter};
+use crate::config::Site;
+use crate::selector_pack;
+
+pub struct YouTube;
+
+impl SiteAdapter for YouTube {
+    fn site(&self) -> Site {
+        Site::YouTube
+    }
+
+    fn matches_host(&self, host: &str) -> bool {
+        is_host_of(host, "youtube.com")
+    }
+
+    fn is_homepage(&self, path: &str) -> bool {
+        path == "/"
+    }
+
+    fn distraction_selectors(&self, path: &str) -> Vec<String> {
+        // The homepage feed, and the watch page's sidebar. These are the ones YouTube keeps renaming,
+        // hence the selector pack.
+        selector_pack::with_active(|pack| match path {
+            "/" => pack.youtube.home_feed.clone(),
+            "/watch" => pack.youtube.watch_sidebar.clone(),
+            _ => Vec::new(),
+        })
+    }
+
+    fn card_selector(&self) -> Option<String> {
+        // Rich items make up the homepage grid, compact ones the watch page sidebar.
+        selector_pack::with_active(|pack| Some(pack.youtube.cards.join(", ")))
+    }
+
+    fn card_info(&self, card: &web_sys::Element) -> Option<MediaInfo> {
+        // Cards that haven't been populated yet don't have a title.
+        let title = text_of(card.query_selector("#video-title"))?;
+        let channel_name = text_of(card.query_selector("ytd-channel-name #text"));
+        let channel_handle = card
+            .query_selector("a[href^='/@']")
+            .ok()
+            .flatten()
+            .and_then(|el| el.get_attribute("href"))
+            .map(|href| href[1..].to_owned());
+
+        Some(MediaInfo {
+            id: None,
+            title,
+            channel_name,
+            channel_handle,
+        })
+    }
+
+    fn current_media(&self, document: &web_sys::Document) -> Option<MediaInfo> {
+        let location = document.location()?;
+        if location.pathname().ok()? != "/watch" {
+            return None;
+        }
+        let search = web_sys::UrlSearchParams::new_with_str(&location.search().ok()?).ok()?;
+
+        let owner = "ytd-watch-metadata #owner";
+        Some(MediaInfo {
+            id: search.get("v"),
+            title: text_of(document.query_selector("ytd-watch-metadata h1")).unwrap_or_default(),
+            channel_name: text_of(
+                document.query_selector(&format!("{owner} ytd-channel-name #text")),
+            ),
+            channel_handle: document
+                .query_selector(&format!("{owner} a[href^='/@']"))
+                .ok()
+                .flatten()
+                .and_then(|el| el.get_attribute("href"))
+                .map(|href| href[1..].to_owned()),
+        })
+    }
+}
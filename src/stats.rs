@@ -0,0 +1,219 @@
+use crate::config::{self, BlockTarget, DayUsage};
+use crate::i18n::t;
+use crate::schedule::WeekTime;
+use std::collections::{BTreeMap, HashMap};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Period {
+    Today,
+    ThisWeek, // Starting Monday, same as `active_days`
+    ThisMonth,
+}
+
+impl Period {
+    pub const ALL: [Period; 3] = [Period::Today, Period::ThisWeek, Period::ThisMonth];
+
+    pub fn label(&self) -> &'static str {
+        match self {
+            Period::Today => t().period_today,
+            Period::ThisWeek => t().period_this_week,
+            Period::ThisMonth => t().period_this_month,
+        }
+    }
+
+    /// `day_key()` of the first day in this period.
+    fn first_day(&self, today: &js_sys::Date) -> String {
+        let first_day = match self {
+            Period::Today => today.clone(),
+            Period::ThisWeek => config::monday_of(today, 0),
+            Period::ThisMonth => js_sys::Date::new_with_year_month_day(
+                today.get_full_year(),
+                today.get_month() as i32,
+                1,
+            ),
+        };
+        config::day_key(&first_day)
+    }
+}
+
+/// Usage of every day in `period`, up to and including today.
+pub fn days_in(
+    daily_usage: &BTreeMap<String, DayUsage>,
+    period: Period,
+) -> impl Iterator<Item = &DayUsage> {
+    let today = js_sys::Date::new_0();
+    let range = period.first_day(&today)..=config::day_key(&today);
+    daily_usage.range(range).map(|(_, usage)| usage)
+}
+
+pub fn total_minutes(daily_usage: &BTreeMap<String, DayUsage>, period: Period) -> u32 {
+    days_in(daily_usage, period).map(|day| day.minutes).sum()
+}
+
+/// (channel, minutes), most watched first.
+pub fn top_channels(
+    daily_usage: &BTreeMap<String, DayUsage>,
+    period: Period,
+    n: usize,
+) -> Vec<(String, u32)> {
+    channels_of(days_in(daily_usage, period), n)
+}
+
+/// Same as `top_channels`, for whichever days.
+pub fn channels_of<'a>(days: impl Iterator<Item = &'a DayUsage>, n: usize) -> Vec<(String, u32)> {
+    let mut channels: HashMap<&str, u32> = HashMap::new();
+    for video in days.flat_map(|day| day.videos.values()) {
+        *channels.entry(&video.channel).or_default() += video.minutes;
+    }
+
+    let channels = channels
+        .into_iter()
+        .map(|(channel, minutes)| (channel.to_owned(), minutes));
+    top_n(channels, n)
+}
+
+/// (title, minutes), most watched first.
+pub fn top_videos(
+    daily_usage: &BTreeMap<String, DayUsage>,
+    period: Period,
+    n: usize,
+) -> Vec<(String, u32)> {
+    // Keyed by ID rather than title, so that two different videos that happen to have the same title
+    // don't get lumped together.
+    let mut videos: HashMap<&str, (&str, u32)> = HashMap::new();
+    for (id, video) in days_in(daily_usage, period).flat_map(|day| day.videos.iter()) {
+        let entry = videos.entry(id).or_insert((&video.title, 0));
+        entry.1 += video.minutes;
+    }
+
+    let videos = videos
+        .into_values()
+        .map(|(title, minutes)| (title.to_owned(), minutes));
+    top_n(videos, n)
+}
+
+/// (site, minutes) for everything that isn't YouTube: the other built-in sites, and the custom sites
+/// with time tracking on (by their match pattern). Most used first.
+pub fn top_other_sites(
+    daily_usage: &BTreeMap<String, DayUsage>,
+    period: Period,
+    n: usize,
+) -> Vec<(String, u32)> {
+    let mut sites: HashMap<String, u32> = HashMap::new();
+    for day in days_in(daily_usage, period) {
+        for (site, minutes) in &day.sites {
+            *sites.entry(site.name().to_owned()).or_default() += minutes;
+        }
+        for (pattern, minutes) in &day.custom_sites {
+            *sites.entry(pattern.clone()).or_default() += minutes;
+        }
+    }
+    top_n(sites.into_iter(), n)
+}
+
+/// (what got blocked, how many times), most blocked first.
+pub fn blocked_counts(
+    daily_usage: &BTreeMap<String, DayUsage>,
+    period: Period,
+) -> Vec<(BlockTarget, u32)> {
+    let mut counts: BTreeMap<BlockTarget, u32> = BTreeMap::new();
+    for (target, count) in days_in(daily_usage, period).flat_map(|day| &day.blocked) {
+        *counts.entry(*target).or_default() += count;
+    }
+    let mut counts: Vec<_> = counts.into_iter().collect();
+    // Stable, so ties stay in `BlockTarget` order.
+    counts.sort_by(|(_, a), (_, b)| b.cmp(a));
+    counts
+}
+
+fn top_n(items: impl Iterator<Item = (String, u32)>, n: usize) -> Vec<(String, u32)> {
+    let mut items: Vec<_> = items.collect();
+    items.sort_by(|(a_name, a_mins), (b_name, b_mins)| {
+        b_mins.cmp(a_mins).then_with(|| a_name.cmp(b_name))
+    });
+    items.truncate(n);
+    items
+}
+
+/// 75 -> "1h 15m", 5 -> "5m"
+pub fn format_minutes(minutes: u32) -> String {
+    match (minutes / 60, minutes % 60) {
+        (0, mins) => format!("{mins}m"),
+        (hours, mins) => format!("{hours}h {mins:02}m"),
+    }
+}
+
+/// Minutes on YouTube by weekday (Monday first) and hour of the day, over the last `weeks` weeks
+/// (this one being the last).
+pub fn hourly_heatmap(daily_usage: &BTreeMap<String, DayUsage>, weeks: u32) -> [[u32; 24]; 7] {
+    let today = js_sys::Date::new_0();
+    let first_day = config::monday_of(&today, weeks - 1);
+
+    let mut heatmap = [[0; 24]; 7];
+    let range = config::day_key(&first_day)..=config::day_key(&today);
+    for (key, day) in daily_usage.range(range) {
+        let Some(date) = config::date_from_key(key) else {
+            continue;
+        };
+        let weekday = WeekTime::from_date(&date).day as usize;
+        for (total, minutes) in heatmap[weekday].iter_mut().zip(&day.hourly) {
+            *total += minutes;
+        }
+    }
+    heatmap
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct BlockWindowSuggestion {
+    pub start_hour: u32,
+    /// Can be past midnight, i.e smaller than `start_hour`.
+    pub end_hour: u32,
+    /// Bitmap, same as `Config::active_days`.
+    pub active_days: u8,
+    /// Percentage of all the minutes in the heatmap that the window would've blocked.
+    pub coverage: u32,
+}
+
+/// The `hours` long window that most of the usage in `heatmap` falls into, on the days that use it
+/// the most. None if there's no usage to go by.
+pub fn suggest_block_window(heatmap: &[[u32; 24]; 7], hours: u32) -> Option<BlockWindowSuggestion> {
+    let hours = hours.clamp(1, 24);
+    let in_window = |start: u32, day: &[u32; 24]| -> u32 {
+        (start..start + hours)
+            .map(|hour| day[(hour % 24) as usize])
+            .sum()
+    };
+
+    let total: u32 = heatmap.iter().flatten().sum();
+    let (start_hour, window_total) = (0..24)
+        .map(|start| {
+            (
+                start,
+                heatmap.iter().map(|day| in_window(start, day)).sum::<u32>(),
+            )
+        })
+        // Earliest one on ties.
+        .max_by(|(a, a_mins), (b, b_mins)| a_mins.cmp(b_mins).then_with(|| b.cmp(a)))?;
+    if window_total == 0 {
+        return None;
+    }
+
+    // Days with at least half of their fair share of the window's usage. There's always at least one
+    // of those, the busiest.
+    let mut active_days = 0u8;
+    let mut covered = 0;
+    for (i, day) in heatmap.iter().enumerate() {
+        let minutes = in_window(start_hour, day);
+        if minutes * 7 * 2 >= window_total {
+            active_days |= 1 << i;
+            covered += minutes;
+        }
+    }
+
+    Some(BlockWindowSuggestion {
+        start_hour,
+        end_hour: (start_hour + hours) % 24,
+        active_days,
+        coverage: covered * 100 / total,
+    })
+}
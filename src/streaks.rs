@@ -0,0 +1,300 @@
+//! Streaks and achievements, all worked out from `Usage::daily_usage` on the spot; nothing extra
+//! gets stored for them.
+//!
+//! Days missing from the history (no YouTube at all) count as good days. Budgets aren't stored per
+//! day, so the current one gets applied to the whole history. The history only goes back
+//! `config::USAGE_KEPT_WEEKS`, and so do the best streaks.
+//!
+//! There's no temporary unlock of the block itself, so the "never unlocked" streak goes by the
+//! closest thing there is: extending the session limit (`DayUsage::session_extensions`).
+
+use crate::config::{self, DayUsage};
+use crate::i18n::t;
+use std::collections::BTreeMap;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum StreakKind {
+    UnderBudget,
+    NoExtensions,
+}
+
+impl StreakKind {
+    pub const ALL: [StreakKind; 2] = [StreakKind::UnderBudget, StreakKind::NoExtensions];
+
+    pub fn label(&self) -> &'static str {
+        match self {
+            StreakKind::UnderBudget => t().streak_under_budget,
+            StreakKind::NoExtensions => t().streak_no_extensions,
+        }
+    }
+
+    /// None if it can't be told, i.e there's no budget to be under.
+    fn is_good(&self, day: Option<&DayUsage>, daily_budget: Option<u32>) -> Option<bool> {
+        let (minutes, extensions) = day.map_or((0, 0), |day| (day.minutes, day.session_extensions));
+        match self {
+            StreakKind::UnderBudget => daily_budget.map(|budget| minutes <= budget),
+            StreakKind::NoExtensions => Some(extensions == 0),
+        }
+    }
+}
+
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub struct Streak {
+    /// Ending today. Today counts as long as it's still good so far.
+    pub current: u32,
+    pub best: u32,
+}
+
+/// `day_key()`s from the first day in the history up to and including today, oldest first.
+fn all_days(daily_usage: &BTreeMap<String, DayUsage>) -> Vec<String> {
+    let today = js_sys::Date::new_0();
+    let today_key = config::day_key(&today);
+    let Some(first) = daily_usage
+        .keys()
+        .next()
+        .and_then(|key| config::date_from_key(key))
+    else {
+        return vec![today_key];
+    };
+
+    let mut days = Vec::new();
+    for offset in 0.. {
+        let date = js_sys::Date::new_with_year_month_day(
+            first.get_full_year(),
+            first.get_month() as i32,
+            first.get_date() as i32 + offset,
+        );
+        let key = config::day_key(&date);
+        if key > today_key {
+            break;
+        }
+        days.push(key);
+    }
+    days
+}
+
+pub fn streak(
+    daily_usage: &BTreeMap<String, DayUsage>,
+    daily_budget: Option<u32>,
+    kind: StreakKind,
+) -> Option<Streak> {
+    streak_over(&all_days(daily_usage), daily_usage, daily_budget, kind)
+}
+
+/// Same as `streak()`, over `days` (oldest first) rather than the whole history up to today.
+fn streak_over(
+    days: &[String],
+    daily_usage: &BTreeMap<String, DayUsage>,
+    daily_budget: Option<u32>,
+    kind: StreakKind,
+) -> Option<Streak> {
+    let mut streak = Streak::default();
+    for day in days {
+        if kind.is_good(daily_usage.get(day), daily_budget)? {
+            streak.current += 1;
+            streak.best = streak.best.max(streak.current);
+        } else {
+            streak.current = 0;
+        }
+    }
+    Some(streak)
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum DayRating {
+    /// Didn't touch YouTube.
+    Unused,
+    Good,
+    /// Over the budget, or extended a session.
+    Bad,
+}
+
+/// (`day_key()`, rating) for the last `weeks` full weeks (Monday to Sunday, the last one being this
+/// week), oldest first. Days after today are left out.
+pub fn calendar(
+    daily_usage: &BTreeMap<String, DayUsage>,
+    daily_budget: Option<u32>,
+    weeks: u32,
+) -> Vec<(String, DayRating)> {
+    let today = js_sys::Date::new_0();
+    let today_key = config::day_key(&today);
+    let first = config::monday_of(&today, weeks - 1);
+
+    (0..7 * weeks as i32)
+        .map(|offset| {
+            let date = js_sys::Date::new_with_year_month_day(
+                first.get_full_year(),
+                first.get_month() as i32,
+                first.get_date() as i32 + offset,
+            );
+            config::day_key(&date)
+        })
+        .filter(|key| *key <= today_key)
+        .map(|key| {
+            let rating = match daily_usage.get(&key) {
+                None => DayRating::Unused,
+                Some(day) if day.minutes == 0 && day.session_extensions == 0 => DayRating::Unused,
+                Some(day) => {
+                    let bad = day.session_extensions > 0
+                        || daily_budget.is_some_and(|budget| day.minutes > budget);
+                    if bad {
+                        DayRating::Bad
+                    } else {
+                        DayRating::Good
+                    }
+                }
+            };
+            (key, rating)
+        })
+        .collect()
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Achievement {
+    FirstSteps,
+    WeekOne,
+    TouchedGrass,
+    Disciplined,
+    IronWill,
+}
+
+impl Achievement {
+    pub const ALL: [Achievement; 5] = [
+        Achievement::FirstSteps,
+        Achievement::WeekOne,
+        Achievement::TouchedGrass,
+        Achievement::Disciplined,
+        Achievement::IronWill,
+    ];
+
+    /// What it takes: a streak of this kind, at least this many days long.
+    fn goal(&self) -> (StreakKind, u32) {
+        match self {
+            Achievement::FirstSteps => (StreakKind::UnderBudget, 3),
+            Achievement::WeekOne => (StreakKind::UnderBudget, 7),
+            Achievement::TouchedGrass => (StreakKind::UnderBudget, 30),
+            Achievement::Disciplined => (StreakKind::NoExtensions, 7),
+            Achievement::IronWill => (StreakKind::NoExtensions, 30),
+        }
+    }
+
+    /// (name, description)
+    pub fn text(&self) -> (&'static str, &'static str) {
+        match self {
+            Achievement::FirstSteps => t().achievement_first_steps,
+            Achievement::WeekOne => t().achievement_week_one,
+            Achievement::TouchedGrass => t().achievement_touched_grass,
+            Achievement::Disciplined => t().achievement_disciplined,
+            Achievement::IronWill => t().achievement_iron_will,
+        }
+    }
+
+    /// Going by the best streak, so they stay unlocked for as long as the history goes back.
+    pub fn is_earned(&self, streaks: &[(StreakKind, Option<Streak>)]) -> bool {
+        let (goal_kind, days) = self.goal();
+        streaks
+            .iter()
+            .any(|(kind, streak)| *kind == goal_kind && streak.is_some_and(|s| s.best >= days))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// October 2026, from the 1st.
+    fn days(n: u32) -> Vec<String> {
+        (1..=n).map(|day| format!("2026-10-{day:02}")).collect()
+    }
+
+    fn usage(days: &[(u32, u32, u32)]) -> BTreeMap<String, DayUsage> {
+        days.iter()
+            .map(|&(day, minutes, session_extensions)| {
+                let usage = DayUsage {
+                    minutes,
+                    session_extensions,
+                    ..Default::default()
+                };
+                (format!("2026-10-{day:02}"), usage)
+            })
+            .collect()
+    }
+
+    #[test]
+    fn streak_continues_through_unused_days() {
+        // The 3rd isn't in the history at all.
+        let daily_usage = usage(&[(1, 30, 0), (2, 60, 0), (4, 10, 0), (5, 0, 0)]);
+        let streak = streak_over(&days(5), &daily_usage, Some(60), StreakKind::UnderBudget);
+        assert_eq!(
+            streak,
+            Some(Streak {
+                current: 5,
+                best: 5
+            })
+        );
+    }
+
+    #[test]
+    fn streak_breaks() {
+        let daily_usage = usage(&[(1, 30, 0), (2, 30, 0), (3, 30, 0), (4, 61, 0), (5, 30, 0)]);
+        let streak = streak_over(&days(5), &daily_usage, Some(60), StreakKind::UnderBudget);
+        assert_eq!(
+            streak,
+            Some(Streak {
+                current: 1,
+                best: 3
+            })
+        );
+
+        // Going over today is what broke it.
+        let streak = streak_over(&days(4), &daily_usage, Some(60), StreakKind::UnderBudget);
+        assert_eq!(
+            streak,
+            Some(Streak {
+                current: 0,
+                best: 3
+            })
+        );
+    }
+
+    #[test]
+    fn extension_streak_ignores_the_budget() {
+        let daily_usage = usage(&[(1, 500, 0), (2, 10, 1), (3, 500, 0)]);
+        let streak = streak_over(&days(3), &daily_usage, Some(60), StreakKind::NoExtensions);
+        assert_eq!(
+            streak,
+            Some(Streak {
+                current: 1,
+                best: 1
+            })
+        );
+        let streak = streak_over(&days(3), &daily_usage, None, StreakKind::NoExtensions);
+        assert!(streak.is_some());
+    }
+
+    #[test]
+    fn no_budget_no_budget_streak() {
+        let daily_usage = usage(&[(1, 30, 0)]);
+        assert_eq!(
+            streak_over(&days(1), &daily_usage, None, StreakKind::UnderBudget),
+            None
+        );
+    }
+
+    #[test]
+    fn achievements_go_by_the_best_streak() {
+        let streaks = [
+            (
+                StreakKind::UnderBudget,
+                Some(Streak {
+                    current: 0,
+                    best: 7,
+                }),
+            ),
+            (StreakKind::NoExtensions, None),
+        ];
+        assert!(Achievement::WeekOne.is_earned(&streaks));
+        assert!(!Achievement::TouchedGrass.is_earned(&streaks));
+        assert!(!Achievement::Disciplined.is_earned(&streaks));
+    }
+}
@@ -1,6 +1,8 @@
 /** @type {import('tailwindcss').Config} */
 module.exports = {
   content: ["./src/**/*.{html,rs}"],
+  // The popup picks light/dark itself (`Config::theme`), by putting `dark` on <html>.
+  darkMode: "class",
   theme: {
     extend: {
       backgroundColor: {